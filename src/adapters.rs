@@ -3,14 +3,117 @@
 //! This module mostly contains types returned from trait methods. Thus you should not need to
 //! worry about it much - just read the documentation of those methods.
 
+mod consumed;
 mod take;
 mod chain;
 mod map_err;
+mod crc32;
+mod adler32;
+mod checked_consume;
+mod decode_iter;
+mod bytes;
+mod flush_every;
+mod coalesce_flush;
+mod escape_ascii;
+mod count_lines;
+mod slices;
+mod ring;
+mod slice_writer;
+mod tracked_slice;
+mod newlines;
+mod xor;
+mod rle;
+mod shared;
+mod multiplex;
+mod length_prefix;
+mod utf8;
+mod line_length;
+mod dedup;
+mod tail;
+mod hexdump;
+mod pad_to;
+mod json;
+mod backfill;
+mod exact_size;
+mod pace;
+mod buf_reader;
+mod buf_writer;
+mod tee;
+mod inspect;
+mod count_write;
+mod position;
+mod write_chain;
+mod broadcast;
+#[cfg(feature = "alloc")]
+mod spill;
+#[cfg(feature = "alloc")]
+mod records;
+#[cfg(feature = "alloc")]
+mod coalesce;
+#[cfg(feature = "alloc")]
+mod transaction;
+#[cfg(feature = "alloc")]
+mod str_ref;
+#[cfg(feature = "alloc")]
+mod demux;
 #[cfg(feature = "std")]
 mod std;
+#[cfg(feature = "heapless")]
+mod heapless;
 
 pub use take::*;
 pub use chain::*;
 pub use map_err::*;
+pub use crc32::*;
+pub use adler32::*;
+pub use checked_consume::*;
+pub use decode_iter::*;
+pub use bytes::*;
+pub use flush_every::*;
+pub use coalesce_flush::*;
+pub use escape_ascii::*;
+pub use count_lines::*;
+pub use slices::*;
+pub use ring::*;
+pub use slice_writer::*;
+pub use tracked_slice::*;
+pub use newlines::*;
+pub use xor::*;
+pub use rle::*;
+pub use shared::*;
+pub use multiplex::*;
+pub use length_prefix::*;
+pub use utf8::*;
+pub use line_length::*;
+pub use dedup::*;
+pub use tail::*;
+pub use hexdump::*;
+pub use pad_to::*;
+pub use json::*;
+pub use backfill::*;
+pub use exact_size::*;
+pub use pace::*;
+pub use buf_reader::*;
+pub use buf_writer::*;
+pub use tee::*;
+pub use inspect::*;
+pub use count_write::*;
+pub use position::*;
+pub use write_chain::*;
+pub use broadcast::*;
+#[cfg(feature = "alloc")]
+pub use spill::*;
+#[cfg(feature = "alloc")]
+pub use records::*;
+#[cfg(feature = "alloc")]
+pub use coalesce::*;
+#[cfg(feature = "alloc")]
+pub use transaction::*;
+#[cfg(feature = "alloc")]
+pub use str_ref::*;
+#[cfg(feature = "alloc")]
+pub use demux::*;
 #[cfg(feature = "std")]
 pub use self::std::*;
+#[cfg(feature = "heapless")]
+pub use self::heapless::*;
@@ -0,0 +1,95 @@
+use crate::BufWrite;
+
+const MOD_ADLER: u32 = 65521;
+/// The largest number of bytes that can be summed without overflowing `u32` before reducing.
+const NMAX: usize = 5552;
+
+/// Computes Adler-32 of all written bytes - returned from [`BufWrite::adler32`].
+pub struct Adler32Writer<W> {
+    writer: W,
+    a: u32,
+    b: u32,
+}
+
+impl<W: BufWrite> Adler32Writer<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Adler32Writer {
+            writer,
+            a: 1,
+            b: 0,
+        }
+    }
+
+    /// Returns Adler-32 of the bytes successfully written so far.
+    pub fn checksum(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    /// Writes the checksum as 4 big-endian bytes (as used by zlib) and returns the inner writer.
+    ///
+    /// The trailer itself is not included in the checksum.
+    pub fn finish_with_trailer(mut self) -> Result<W, W::WriteError> {
+        let checksum = self.checksum();
+        self.writer.write_all(&checksum.to_be_bytes())?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: BufWrite> BufWrite for Adler32Writer<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        for chunk in bytes.chunks(NMAX) {
+            for &byte in chunk {
+                self.a += u32::from(byte);
+                self.b += self.a;
+            }
+            self.a %= MOD_ADLER;
+            self.b %= MOD_ADLER;
+        }
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn adler32() {
+        let mut buf = [0u8; 13];
+        let mut writer = (&mut buf[..]).adler32();
+        writer.write_all(b"Wiki").unwrap();
+        writer.write_all(b"pedia").unwrap();
+        assert_eq!(writer.checksum(), 0x11E6_0398);
+        writer.finish_with_trailer().unwrap();
+        assert_eq!(buf[9..], [0x11, 0xE6, 0x03, 0x98]);
+    }
+
+    #[test]
+    fn adler32_long() {
+        let mut writer = crate::sink().adler32();
+        for _ in 0..100 {
+            writer.write_all(&[0xFF; 1000]).unwrap_or_else(|infallible| match infallible {});
+        }
+        let (mut a, mut b) = (1u32, 0u32);
+        for _ in 0..100_000 {
+            a = (a + 0xFF) % 65521;
+            b = (b + a) % 65521;
+        }
+        assert_eq!(writer.checksum(), (b << 16) | a);
+    }
+}
@@ -0,0 +1,113 @@
+use core::convert::TryFrom;
+use crate::{BufWrite, Seek, SeekFrom};
+use crate::error::BackfillError;
+
+/// Position of a reserved field - returned from [`BackfillWriter::reserve_u32`].
+///
+/// It must only be passed to the writer that returned it.
+#[derive(Debug)]
+#[must_use = "the placeholder should be filled"]
+pub struct Placeholder {
+    offset: u64,
+}
+
+/// Writes placeholders and fills them in later - returned from [`BufWrite::backfill`].
+///
+/// The writer tracks the number of bytes written through it so the placeholders can be found
+/// using relative seeks.
+pub struct BackfillWriter<W> {
+    writer: W,
+    written: u64,
+}
+
+impl<W: BufWrite + Seek> BackfillWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        BackfillWriter {
+            writer,
+            written: 0,
+        }
+    }
+
+    /// Writes four zero bytes and returns their position.
+    pub fn reserve_u32(&mut self) -> Result<Placeholder, W::WriteError> {
+        let offset = self.written;
+        self.write_all(&[0; 4])?;
+        Ok(Placeholder {
+            offset,
+        })
+    }
+
+    /// Overwrites the placeholder with big-endian `value` and returns to the current position.
+    ///
+    /// # Errors
+    ///
+    /// If writing or seeking fails the position of the underlying writer is unspecified.
+    pub fn fill(&mut self, placeholder: Placeholder, value: u32) -> Result<(), BackfillError<W::WriteError, W::SeekError>> {
+        self.fill_bytes(placeholder, value.to_be_bytes())
+    }
+
+    /// Overwrites the placeholder with little-endian `value` and returns to the current position.
+    ///
+    /// # Errors
+    ///
+    /// If writing or seeking fails the position of the underlying writer is unspecified.
+    pub fn fill_le(&mut self, placeholder: Placeholder, value: u32) -> Result<(), BackfillError<W::WriteError, W::SeekError>> {
+        self.fill_bytes(placeholder, value.to_le_bytes())
+    }
+
+    /// Returns the number of bytes written through this adapter.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn fill_bytes(&mut self, placeholder: Placeholder, bytes: [u8; 4]) -> Result<(), BackfillError<W::WriteError, W::SeekError>> {
+        let distance = i64::try_from(self.written - placeholder.offset).expect("stream too long");
+        self.writer.seek(SeekFrom::Current(-distance)).map_err(BackfillError::SeekingFailed)?;
+        self.writer.write_all(&bytes).map_err(BackfillError::WritingFailed)?;
+        self.writer.seek(SeekFrom::Current(distance - 4)).map_err(BackfillError::SeekingFailed)?;
+        Ok(())
+    }
+}
+
+impl<W: BufWrite + Seek> BufWrite for BackfillWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn backfill_length() {
+        let mut writer = std::io::Cursor::new([0u8; 10]).backfill();
+        writer.write_all(b"x").unwrap();
+        let len = writer.reserve_u32().unwrap();
+        writer.write_all(b"body").unwrap();
+        writer.fill(len, 4).unwrap();
+        writer.write_all(b"!").unwrap();
+        assert_eq!(&writer.into_inner().into_inner(), b"x\x00\x00\x00\x04body!");
+    }
+}
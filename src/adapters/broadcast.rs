@@ -0,0 +1,85 @@
+use crate::BufWrite;
+
+/// Writes all bytes to two writers - returned from [`BufWrite::broadcast`].
+///
+/// Each write goes to the first writer and then to the second one. If writing into the first
+/// writer fails the error is returned immediately and the second writer is not touched. If
+/// writing into the second writer fails the bytes written into the first one stay written.
+pub struct Broadcast<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: BufWrite, B: BufWrite<WriteError=A::WriteError>> Broadcast<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Broadcast {
+            first,
+            second,
+        }
+    }
+
+    /// Returns the underlying writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: BufWrite, B: BufWrite<WriteError=A::WriteError>> BufWrite for Broadcast<A, B> {
+    type WriteError = A::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.first.write_all(bytes)?;
+        self.second.write_all(bytes)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        self.first.write_byte(byte)?;
+        self.second.write_byte(byte)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.first.reserve(additional);
+        self.second.reserve(additional);
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.first.flush()?;
+        self.second.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.first.needs_flush() || self.second.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn both() {
+        let mut first = [0u8; 4];
+        let mut second = [0u8; 4];
+        let mut writer = (&mut first[..]).broadcast(&mut second[..]);
+        writer.write_all(&[1, 2]).unwrap();
+        writer.write_byte(3).unwrap();
+        writer.flush().unwrap();
+        let (first_rest, second_rest) = writer.into_inner();
+        assert_eq!(first_rest.len(), 1);
+        assert_eq!(second_rest.len(), 1);
+        assert_eq!(first, [1, 2, 3, 0]);
+        assert_eq!(second, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn failing_first() {
+        let mut first = [0u8; 2];
+        let mut second = [0u8; 4];
+        let mut writer = (&mut first[..]).broadcast(&mut second[..]);
+        writer.write_all(&[1, 2]).unwrap();
+        assert!(writer.write_all(&[3]).is_err());
+        let (_, second_rest) = writer.into_inner();
+        assert_eq!(second_rest.len(), 2);
+        assert_eq!(second, [1, 2, 0, 0]);
+    }
+}
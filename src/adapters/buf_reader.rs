@@ -0,0 +1,131 @@
+use crate::{BufRead, RawRead};
+
+/// Adds buffering to an unbuffered source.
+///
+/// The buffer is an array of `N` bytes so this doesn't allocate.
+pub struct BufReader<R, const N: usize> {
+    reader: R,
+    buf: [u8; N],
+    pos: usize,
+    len: usize,
+}
+
+impl<R: RawRead, const N: usize> BufReader<R, N> {
+    /// Creates the reader with an empty buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero since such reader could never return any bytes.
+    pub fn new(reader: R) -> Self {
+        assert!(N > 0, "the buffer of BufReader must not be empty");
+        BufReader {
+            reader,
+            buf: [0; N],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader skips the buffered bytes.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that the buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: RawRead, const N: usize> BufRead for BufReader<R, N> {
+    type ReadError = R::ReadError;
+
+    /// Returns the buffered bytes, reading more if there are none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`RawRead::read_raw`] claims to have read more bytes than the buffer size.
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.pos == self.len {
+            let len = self.reader.read_raw(&mut self.buf)?;
+            assert!(len <= N, "RawRead::read_raw returned {} but the buffer has only {} bytes", len, N);
+            self.len = len;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.len - self.pos);
+        self.pos += amount;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, RawRead};
+    use super::BufReader;
+
+    struct Chunks<'a> {
+        data: &'a [u8],
+        max: usize,
+    }
+
+    impl RawRead for Chunks<'_> {
+        type ReadError = core::convert::Infallible;
+
+        fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize, Self::ReadError> {
+            let len = buf.len().min(self.max).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn buffered() {
+        let mut reader = BufReader::<_, 4>::new(Chunks { data: &[1, 2, 3, 4, 5, 6, 7], max: 3 });
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [1, 2, 3]);
+        reader.consume(2);
+        assert_eq!(reader.buffer(), [3]);
+        let mut buf = [0; 5];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [3, 4, 5, 6, 7]);
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn zero_size() {
+        BufReader::<_, 0>::new(Chunks { data: &[1], max: 1 });
+    }
+
+    #[test]
+    #[should_panic(expected = "returned 5 but the buffer has only 4 bytes")]
+    fn oversized_read() {
+        struct Liar;
+
+        impl RawRead for Liar {
+            type ReadError = core::convert::Infallible;
+
+            fn read_raw(&mut self, _buf: &mut [u8]) -> Result<usize, Self::ReadError> {
+                Ok(5)
+            }
+        }
+
+        let _ = BufReader::<_, 4>::new(Liar).fill_buf();
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{BufWrite, RawWrite};
+
+/// Batches small writes to an unbuffered sink.
+///
+/// The buffer is an array of `N` bytes so this doesn't allocate. The bytes are written to the
+/// sink when the buffer is full or when [`flush`](BufWrite::flush) is called. Writes larger than
+/// the buffer bypass it.
+///
+/// **Important:** the buffer is **not** flushed when the writer is dropped since flushing can
+/// fail and the error would be lost. Call [`flush`](BufWrite::flush) before dropping the writer,
+/// otherwise the buffered bytes are lost.
+pub struct BufWriter<W, const N: usize> {
+    writer: W,
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<W: RawWrite, const N: usize> BufWriter<W, N> {
+    /// Creates the writer with an empty buffer.
+    pub fn new(writer: W) -> Self {
+        BufWriter {
+            writer,
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the bytes that were not written to the sink yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer skips the buffered bytes.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// Note that the buffered bytes are lost, call [`flush`](BufWrite::flush) first.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: RawWrite, const N: usize> BufWrite for BufWriter<W, N> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        if bytes.len() > N - self.len {
+            self.flush()?;
+            if bytes.len() >= N {
+                return self.writer.write_raw(bytes);
+            }
+        }
+        self.buf[self.len..(self.len + bytes.len())].copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    /// Writes the buffered bytes to the sink.
+    ///
+    /// The bytes are kept in the buffer if writing fails.
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        if self.len > 0 {
+            self.writer.write_raw(&self.buf[..self.len])?;
+            self.len = 0;
+        }
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.len > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufWrite, RawWrite};
+    use super::BufWriter;
+
+    struct Recorder {
+        data: [u8; 16],
+        len: usize,
+        calls: usize,
+    }
+
+    impl RawWrite for Recorder {
+        type WriteError = core::convert::Infallible;
+
+        fn write_raw(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+            self.data[self.len..(self.len + bytes.len())].copy_from_slice(bytes);
+            self.len += bytes.len();
+            self.calls += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn partial_fills() {
+        let mut writer = BufWriter::<_, 4>::new(Recorder { data: [0; 16], len: 0, calls: 0 });
+        writer.write_all(&[1, 2]).unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(&[3, 4]).unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.get_ref().calls, 0);
+        writer.write_all(&[5]).unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.get_ref().calls, 1);
+        assert_eq!(writer.buffer(), [5]);
+        assert!(writer.needs_flush());
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        let recorder = writer.into_inner();
+        assert_eq!(recorder.calls, 2);
+        assert_eq!(recorder.data[..recorder.len], [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn oversized_writes() {
+        let mut writer = BufWriter::<_, 4>::new(Recorder { data: [0; 16], len: 0, calls: 0 });
+        writer.write_all(&[1]).unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(&[2, 3, 4, 5, 6]).unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.get_ref().calls, 2);
+        assert!(writer.buffer().is_empty());
+        writer.write_all(&[7]).unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        let recorder = writer.into_inner();
+        assert_eq!(recorder.calls, 3);
+        assert_eq!(recorder.data[..recorder.len], [1, 2, 3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::BufRead;
+
+/// Iterator over the bytes of a reader - returned from [`BufRead::bytes`].
+pub struct Bytes<R> {
+    reader: R,
+}
+
+impl<R: BufRead> Bytes<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Bytes {
+            reader,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for Bytes<R> {
+    type Item = Result<u8, R::ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.read_byte().transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.size_hint()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+    use crate::BufRead;
+
+    #[test]
+    fn collect_bytes() {
+        let bytes = (&[1u8, 2, 3][..]).bytes().collect::<Vec<Result<u8, Infallible>>>();
+        assert_eq!(bytes, [Ok(1), Ok(2), Ok(3)]);
+        assert!((&[][..]).bytes().next().is_none());
+    }
+}
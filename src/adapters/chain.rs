@@ -42,6 +42,93 @@ impl<L: BufRead, R: BufRead<ReadError=L::ReadError>> BufRead for Chain<L, R> {
     }
 }
 
+/// Concatenates bytes from a tuple of readers - returned from [`crate::chain_tuple`].
+///
+/// The readers are read one after another, each until it returns an empty buffer. Readers that
+/// already ended are not touched again.
+pub struct TupleChain<T> {
+    readers: T,
+    current: usize,
+}
+
+impl<T> TupleChain<T> {
+    pub(crate) fn new(readers: T) -> Self {
+        TupleChain {
+            readers,
+            current: 0,
+        }
+    }
+
+    /// Returns the underlying readers.
+    pub fn into_inner(self) -> T {
+        self.readers
+    }
+}
+
+/// Implements `BufRead` for `TupleChain` of a tuple with given elements.
+macro_rules! impl_tuple_chain {
+    ($($name:ident . $index:tt,)* ; $last:ident . $last_index:tt) => {
+        impl<Error, $($name: BufRead<ReadError=Error>,)* $last: BufRead<ReadError=Error>> BufRead for TupleChain<($($name,)* $last,)> {
+            type ReadError = Error;
+
+            fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+                let readers = &mut self.readers;
+                let current = &mut self.current;
+                let mut buf: &[u8] = &[];
+                $(
+                    if *current == $index {
+                        buf = readers.$index.fill_buf()?;
+                        if buf.is_empty() {
+                            *current += 1;
+                        }
+                    }
+                )*
+                if *current == $last_index {
+                    buf = readers.$last_index.fill_buf()?;
+                }
+                Ok(buf)
+            }
+
+            fn consume(&mut self, amount: usize) {
+                match self.current {
+                    $($index => self.readers.$index.consume(amount),)*
+                    _ => self.readers.$last_index.consume(amount),
+                }
+            }
+
+            fn buffer(&self) -> &[u8] {
+                match self.current {
+                    $($index => self.readers.$index.buffer(),)*
+                    _ => self.readers.$last_index.buffer(),
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let (min, max) = self.readers.$last_index.size_hint();
+                $(
+                    let (min, max) = if self.current <= $index {
+                        let (cur_min, cur_max) = self.readers.$index.size_hint();
+                        let max = match (max, cur_max) {
+                            (Some(max), Some(cur_max)) => max.checked_add(cur_max),
+                            _ => None,
+                        };
+                        (min.saturating_add(cur_min), max)
+                    } else {
+                        (min, max)
+                    };
+                )*
+                (min, max)
+            }
+        }
+    }
+}
+
+impl_tuple_chain!(A.0, ; B.1);
+impl_tuple_chain!(A.0, B.1, ; C.2);
+impl_tuple_chain!(A.0, B.1, C.2, ; D.3);
+impl_tuple_chain!(A.0, B.1, C.2, D.3, ; E.4);
+impl_tuple_chain!(A.0, B.1, C.2, D.3, E.4, ; F.5);
+
 #[cfg(test)]
 mod test {
     use crate::BufRead;
@@ -82,4 +169,61 @@ mod test {
         assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[]);
         assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[]);
     }
+
+    /// Reader returning the given chunks and then counting the calls to `fill_buf` after the end.
+    struct Chunks<'a> {
+        chunks: &'a [&'a [u8]],
+        calls_after_end: usize,
+    }
+
+    impl BufRead for Chunks<'_> {
+        type ReadError = core::convert::Infallible;
+
+        fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+            match self.chunks.first() {
+                Some(chunk) => Ok(chunk),
+                None => {
+                    self.calls_after_end += 1;
+                    Ok(&[])
+                },
+            }
+        }
+
+        fn consume(&mut self, amount: usize) {
+            if amount > 0 {
+                assert_eq!(amount, self.chunks[0].len());
+                self.chunks = &self.chunks[1..];
+            }
+        }
+    }
+
+    #[test]
+    fn tuple_chain() {
+        let mut reader = crate::chain_tuple((&[1, 2][..], &[][..], &[3][..]));
+        assert_eq!(reader.size_hint(), (3, Some(3)));
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf).unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(reader.size_hint(), (1, Some(1)));
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [3]);
+        assert_eq!(reader.buffer(), [3]);
+        reader.consume(1);
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+    }
+
+    #[test]
+    fn tuple_chain_ended_not_refilled() {
+        let first = Chunks { chunks: &[&[1], &[2]], calls_after_end: 0, };
+        let second = Chunks { chunks: &[&[3]], calls_after_end: 0, };
+        let mut reader = crate::chain_tuple((first, second));
+        for expected in 1..=3 {
+            assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [expected]);
+            reader.consume(1);
+        }
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+        let (first, second) = reader.into_inner();
+        assert_eq!(first.calls_after_end, 1);
+        assert_eq!(second.calls_after_end, 2);
+    }
 }
@@ -0,0 +1,77 @@
+use crate::BufRead;
+use crate::error::ConsumeError;
+
+/// Validates the amount passed to `consume` - returned from [`BufRead::checked_consume`].
+pub struct CheckedConsume<R> {
+    reader: R,
+    available: usize,
+}
+
+impl<R: BufRead> CheckedConsume<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        CheckedConsume {
+            reader,
+            available: 0,
+        }
+    }
+
+    /// Consumes `amount` bytes if they are available in the buffer returned by the last call to
+    /// `fill_buf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error without consuming anything if `amount` exceeds the number of bytes that
+    /// were returned by the last `fill_buf` and not consumed yet.
+    pub fn try_consume(&mut self, amount: usize) -> Result<(), ConsumeError> {
+        if amount > self.available {
+            return Err(ConsumeError::new(amount, self.available));
+        }
+        self.available -= amount;
+        self.reader.consume(amount);
+        Ok(())
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for CheckedConsume<R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        let buf = self.reader.fill_buf()?;
+        self.available = buf.len();
+        Ok(buf)
+    }
+
+    /// Consumes `amount` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `amount` is larger than the number of available bytes. Use
+    /// [`try_consume`](Self::try_consume) to handle the error instead.
+    fn consume(&mut self, amount: usize) {
+        if let Err(error) = self.try_consume(amount) {
+            panic!("{}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+
+    #[test]
+    fn try_consume() {
+        let mut reader = (&[1, 2, 3][..]).checked_consume();
+        assert!(reader.try_consume(1).is_err());
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[1, 2, 3]);
+        reader.try_consume(2).unwrap();
+        assert!(reader.try_consume(2).is_err());
+        reader.try_consume(1).unwrap();
+        assert!(reader.try_consume(1).is_err());
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[]);
+    }
+}
@@ -0,0 +1,92 @@
+use alloc::vec::Vec;
+use crate::BufWrite;
+
+/// Coalesces small writes into larger chunks - returned from [`BufWrite::coalesce`].
+pub struct Coalesce<W> {
+    writer: W,
+    min_chunk: usize,
+    buffer: Vec<u8>,
+}
+
+impl<W: BufWrite> Coalesce<W> {
+    pub(crate) fn new(writer: W, min_chunk: usize) -> Self {
+        Coalesce {
+            writer,
+            min_chunk,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the number of bytes that were not forwarded to the underlying writer yet.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// Note that the buffered bytes are lost, call [`flush`](BufWrite::flush) first if you want
+    /// to write them.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for Coalesce<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        if self.buffer.is_empty() && bytes.len() >= self.min_chunk {
+            return self.writer.write_all(bytes);
+        }
+        self.buffer.extend_from_slice(bytes);
+        if self.buffer.len() >= self.min_chunk {
+            self.writer.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered bytes regardless of their amount and flushes the underlying writer.
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        if !self.buffer.is_empty() {
+            self.writer.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use crate::BufWrite;
+
+    #[test]
+    fn coalesce() {
+        struct Chunks(Vec<usize>);
+
+        impl BufWrite for Chunks {
+            type WriteError = core::convert::Infallible;
+
+            fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+                self.0.push(bytes.len());
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+        }
+
+        let mut writer = Chunks(Vec::new()).coalesce(4);
+        writer.write_all(&[0; 10]).unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(&[0; 2]).unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(&[0; 1]).unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.buffered(), 3);
+        writer.write_all(&[0; 2]).unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.buffered(), 0);
+        writer.write_all(&[0; 1]).unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.into_inner().0, [10, 5, 1]);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::BufWrite;
+
+/// Skips flushes when nothing was written since the last one - returned from
+/// [`BufWrite::coalesce_flush`].
+pub struct CoalesceFlush<W> {
+    writer: W,
+    dirty: bool,
+}
+
+impl<W: BufWrite> CoalesceFlush<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        CoalesceFlush {
+            writer,
+            // The writer may contain unflushed data already.
+            dirty: true,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for CoalesceFlush<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.dirty = true;
+        self.writer.write_all(bytes)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        if self.dirty {
+            self.writer.flush()?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.dirty && self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn coalesce_flush() {
+        struct Flushes(usize);
+
+        impl BufWrite for Flushes {
+            type WriteError = core::convert::Infallible;
+
+            fn write_all(&mut self, _bytes: &[u8]) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                self.0 += 1;
+                Ok(())
+            }
+        }
+
+        let mut writer = Flushes(0).coalesce_flush();
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(b"a").unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.into_inner().0, 2);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::BufRead;
+
+/// Returns the first `len` bytes of the buffer previously returned by `fill_buf` of `reader`.
+///
+/// The bytes are taken from [`BufRead::buffer`] if it contains enough of them so that no IO is
+/// performed. Otherwise `fill_buf` is called again which may fail.
+///
+/// # Panics
+///
+/// Panics if `fill_buf` returns fewer than `len` bytes.
+pub(crate) fn filled_prefix<R: BufRead + ?Sized>(reader: &mut R, len: usize) -> Result<&[u8], R::ReadError> {
+    if R::buffer(reader).len() >= len {
+        return Ok(&R::buffer(reader)[..len]);
+    }
+    let buf = reader.fill_buf()?;
+    assert!(buf.len() >= len, "the reader returned {} bytes but {} bytes were available previously", buf.len(), len);
+    Ok(&buf[..len])
+}
+
+/// Reader giving access to the consumed bytes, used by adapters processing them.
+///
+/// If the consumed bytes can not be retrieved the error is stored and returned from the next call
+/// to [`fill_buf`](Self::fill_buf).
+pub(crate) struct ConsumedBytes<R: BufRead> {
+    reader: R,
+    error: Option<R::ReadError>,
+}
+
+impl<R: BufRead> ConsumedBytes<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        ConsumedBytes {
+            reader,
+            error: None,
+        }
+    }
+
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// The pending error, if any, is lost.
+    pub(crate) fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Returns the pending error, if any, or fills the buffer of the underlying reader.
+    pub(crate) fn fill_buf(&mut self) -> Result<&[u8], R::ReadError> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.reader.fill_buf()
+    }
+
+    /// Consumes `amount` bytes without accessing them.
+    pub(crate) fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount)
+    }
+
+    /// Consumes `amount` bytes passing them to `f` first.
+    ///
+    /// `f` is not called if retrieving the bytes failed, the error is stored instead.
+    pub(crate) fn consume_with<F: FnOnce(&[u8])>(&mut self, amount: usize, f: F) {
+        if amount > 0 {
+            match filled_prefix(&mut self.reader, amount) {
+                Ok(bytes) => f(bytes),
+                Err(error) => self.error = Some(error),
+            }
+        }
+        self.reader.consume(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+    use super::ConsumedBytes;
+
+    /// Reader that doesn't implement `buffer` and fails the second `fill_buf`.
+    struct FailingRefill {
+        fills: usize,
+    }
+
+    impl BufRead for FailingRefill {
+        type ReadError = ();
+
+        fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+            self.fills += 1;
+            if self.fills > 1 {
+                return Err(());
+            }
+            Ok(&[1, 2, 3])
+        }
+
+        fn consume(&mut self, _amount: usize) {}
+    }
+
+    #[test]
+    fn uses_buffer() {
+        let mut reader = ConsumedBytes::new(&[1, 2, 3][..]);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [1, 2, 3]);
+        let mut consumed = [0; 2];
+        reader.consume_with(2, |bytes| consumed.copy_from_slice(bytes));
+        assert_eq!(consumed, [1, 2]);
+        assert_eq!(reader.into_inner(), [3]);
+    }
+
+    #[test]
+    fn reports_refill_error() {
+        let mut reader = ConsumedBytes::new(FailingRefill { fills: 0 });
+        assert_eq!(reader.fill_buf(), Ok(&[1, 2, 3][..]));
+        reader.consume_with(2, |_| panic!("the bytes were not available"));
+        assert_eq!(reader.fill_buf(), Err(()));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{BufRead, BufWrite};
+use super::consumed::ConsumedBytes;
+
+fn count_newlines(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|&&byte| byte == b'\n').count() as u64
+}
+
+/// Counts lines written to the writer - returned from [`BufWrite::count_lines_write`].
+pub struct LineCountWriter<W> {
+    writer: W,
+    lines: u64,
+}
+
+impl<W: BufWrite> LineCountWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        LineCountWriter {
+            writer,
+            lines: 0,
+        }
+    }
+
+    /// Returns the number of newline (`\n`) bytes successfully written so far.
+    pub fn lines(&self) -> u64 {
+        self.lines
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for LineCountWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        self.lines += count_newlines(bytes);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+/// Counts lines consumed from the reader - returned from [`BufRead::count_lines`].
+///
+/// Only consumed bytes are counted so peeking at the buffer doesn't affect the count.
+///
+/// If the consumed bytes can not be retrieved from the underlying reader without IO and getting
+/// them fails, they are not counted and the error is returned from the next call to
+/// [`fill_buf`](BufRead::fill_buf).
+pub struct LineCountReader<R: BufRead> {
+    reader: ConsumedBytes<R>,
+    lines: u64,
+}
+
+impl<R: BufRead> LineCountReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        LineCountReader {
+            reader: ConsumedBytes::new(reader),
+            lines: 0,
+        }
+    }
+
+    /// Returns the number of newline (`\n`) bytes consumed so far.
+    ///
+    /// Adding one to this gives the current line number.
+    pub fn lines(&self) -> u64 {
+        self.lines
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: BufRead> BufRead for LineCountReader<R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        let lines = &mut self.lines;
+        self.reader.consume_with(amount, |bytes| *lines += count_newlines(bytes))
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.get_ref().buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.get_ref().size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.get_ref().stream_position_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+
+    #[test]
+    fn count_consumed_lines() {
+        let mut reader = (&b"foo\nbar\n\nbaz"[..]).count_lines();
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).len(), 12);
+        assert_eq!(reader.lines(), 0);
+        reader.consume(4);
+        assert_eq!(reader.lines(), 1);
+        reader.consume(5);
+        assert_eq!(reader.lines(), 3);
+        reader.consume(3);
+        assert_eq!(reader.lines(), 3);
+    }
+
+    #[test]
+    fn count_written_lines() {
+        let mut writer = crate::sink().count_lines_write();
+        writer.write_all(b"foo\nbar").unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(b"\n\nbaz").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.lines(), 3);
+    }
+}
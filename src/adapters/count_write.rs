@@ -0,0 +1,68 @@
+use crate::BufWrite;
+
+/// Counts the written bytes - returned from [`BufWrite::count`].
+pub struct CountWrite<W> {
+    writer: W,
+    written: u64,
+}
+
+impl<W: BufWrite> CountWrite<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        CountWrite {
+            writer,
+            written: 0,
+        }
+    }
+
+    /// Returns the number of bytes successfully written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for CountWrite<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        let written = self.writer.write_some(bytes)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn measure_size() {
+        let mut writer = crate::sink().count();
+        writer.write_byte(1).unwrap_or_else(|infallible| match infallible {});
+        writer.write_u32_le(42).unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(b"hello").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.bytes_written(), 10);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{BufRead, BufWrite, Endianness};
+use crate::error::{ChecksumMismatch, ChecksumReadError, UnexpectedEnd};
+use super::consumed::ConsumedBytes;
+
+/// Lookup table for the reflected CRC32 (IEEE 802.3) polynomial.
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Updates the (non-inverted) CRC32 `state` with `bytes`.
+fn crc32_update(mut state: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        state = CRC32_TABLE[((state ^ u32::from(byte)) & 0xFF) as usize] ^ (state >> 8);
+    }
+    state
+}
+
+/// Computes CRC32 of all written bytes - returned from [`BufWrite::crc32`].
+pub struct Crc32Writer<W> {
+    writer: W,
+    state: u32,
+}
+
+impl<W: BufWrite> Crc32Writer<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Crc32Writer {
+            writer,
+            state: 0xFFFF_FFFF,
+        }
+    }
+
+    /// Returns CRC32 of the bytes successfully written so far.
+    pub fn checksum(&self) -> u32 {
+        !self.state
+    }
+
+    /// Writes the checksum as 4 little-endian bytes and returns the inner writer.
+    ///
+    /// The trailer itself is not included in the checksum.
+    pub fn finish_with_trailer(mut self) -> Result<W, W::WriteError> {
+        let checksum = self.checksum();
+        self.writer.write_all(&checksum.to_le_bytes())?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: BufWrite> BufWrite for Crc32Writer<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        self.state = crc32_update(self.state, bytes);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+const TRAILER_LEN: usize = 4;
+
+/// Verifies CRC32 trailer of the stream - returned from [`BufRead::verify_crc32`].
+///
+/// The last four bytes of the underlying stream are never returned from `fill_buf`. Once the end
+/// is reached they are compared with the checksum of the preceding bytes and
+/// [`ChecksumReadError::ChecksumMismatch`] is returned if they differ.
+///
+/// The bytes are hashed when they are consumed. If they can not be retrieved from the underlying
+/// reader without IO and getting them fails, the read error is returned from the next call to
+/// [`fill_buf`](BufRead::fill_buf).
+pub struct Crc32Reader<R: BufRead> {
+    reader: ConsumedBytes<R>,
+    state: u32,
+    endianness: Endianness,
+    // Bytes taken out of the reader because it returned too few of them to decide whether they
+    // belong to the trailer. They logically precede the contents of `reader`.
+    staged: [u8; 2 * TRAILER_LEN],
+    staged_start: usize,
+    staged_end: usize,
+}
+
+impl<R: BufRead> Crc32Reader<R> {
+    pub(crate) fn new(reader: R, endianness: Endianness) -> Self {
+        Crc32Reader {
+            reader: ConsumedBytes::new(reader),
+            state: 0xFFFF_FFFF,
+            endianness,
+            staged: [0; 2 * TRAILER_LEN],
+            staged_start: 0,
+            staged_end: 0,
+        }
+    }
+
+    /// Returns CRC32 of the data bytes consumed so far.
+    pub fn checksum(&self) -> u32 {
+        !self.state
+    }
+
+    fn stage(&mut self) -> Result<(), R::ReadError> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            let staged = self.staged_end - self.staged_start;
+            if buf.is_empty() || staged > TRAILER_LEN || (staged == 0 && buf.len() > TRAILER_LEN) || (staged > 0 && buf.len() >= TRAILER_LEN) {
+                break Ok(());
+            }
+
+            // staged <= 4 && buf.len() <= 4 here and if staged > 0 then buf.len() < 4 so it fits
+            self.staged.copy_within(self.staged_start..self.staged_end, 0);
+            self.staged_start = 0;
+            self.staged_end = staged + buf.len();
+            self.staged[staged..self.staged_end].copy_from_slice(buf);
+            let len = buf.len();
+            self.reader.consume(len);
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for Crc32Reader<R> {
+    type ReadError = ChecksumReadError<R::ReadError>;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        self.stage().map_err(ChecksumReadError::ReadingFailed)?;
+
+        let staged = self.staged_end - self.staged_start;
+        if staged > TRAILER_LEN {
+            return Ok(&self.staged[self.staged_start..(self.staged_end - TRAILER_LEN)]);
+        }
+        let buf = self.reader.fill_buf().map_err(ChecksumReadError::ReadingFailed)?;
+        if !buf.is_empty() {
+            if staged > 0 {
+                return Ok(&self.staged[self.staged_start..self.staged_end]);
+            }
+            return Ok(&buf[..(buf.len() - TRAILER_LEN)]);
+        }
+
+        if staged < TRAILER_LEN {
+            return Err(UnexpectedEnd::new(TRAILER_LEN, staged).into());
+        }
+        let mut trailer = [0; TRAILER_LEN];
+        trailer.copy_from_slice(&self.staged[self.staged_start..self.staged_end]);
+        let expected = match self.endianness {
+            Endianness::Little => u32::from_le_bytes(trailer),
+            Endianness::Big => u32::from_be_bytes(trailer),
+        };
+        if expected != !self.state {
+            return Err(ChecksumMismatch::new(expected, !self.state).into());
+        }
+        Ok(&[])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if self.staged_end > self.staged_start {
+            let end = self.staged_start + amount;
+            debug_assert!(end <= self.staged_end);
+            self.state = crc32_update(self.state, &self.staged[self.staged_start..end]);
+            self.staged_start = end;
+        } else {
+            let state = &mut self.state;
+            self.reader.consume_with(amount, |bytes| *state = crc32_update(*state, bytes));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite, Endianness};
+    use crate::error::ChecksumReadError;
+
+    #[test]
+    fn crc32_check_value() {
+        let mut buf = [0; 13];
+        let mut writer = (&mut buf as &mut [_]).crc32();
+        writer.write_all(b"1234").unwrap();
+        writer.write_all(b"56789").unwrap();
+        assert_eq!(writer.checksum(), 0xCBF4_3926);
+        writer.finish_with_trailer().unwrap();
+        assert_eq!(&buf[..9], b"123456789");
+        assert_eq!(buf[9..], 0xCBF4_3926u32.to_le_bytes());
+    }
+
+    #[test]
+    fn crc32_verify_ok() {
+        let data = b"123456789\x26\x39\xF4\xCB";
+        let mut reader = (&data[..]).verify_crc32(Endianness::Little);
+        assert_eq!(reader.fill_buf().unwrap(), b"123456789");
+        reader.consume(9);
+        assert_eq!(reader.fill_buf().unwrap(), b"");
+    }
+
+    #[test]
+    fn crc32_verify_byte_by_byte() {
+        let data = b"123456789\xCB\xF4\x39\x26";
+        let mut reader = (&data[..]).take(1).chain(&data[1..]).verify_crc32(Endianness::Big);
+        for &byte in b"123456789" {
+            assert_eq!(reader.read_byte().unwrap(), Some(byte));
+        }
+        assert!(reader.read_byte().unwrap().is_none());
+    }
+
+    #[test]
+    fn crc32_verify_hashes_consumed() {
+        let data = b"123456789\x26\x39\xF4\xCB";
+        let mut reader = (&data[..]).verify_crc32(Endianness::Little);
+        assert_eq!(reader.fill_buf().unwrap(), b"123456789");
+        assert_eq!(reader.fill_buf().unwrap(), b"123456789");
+        assert_eq!(reader.checksum(), 0);
+        reader.consume(4);
+        let mut buf = [0u8; 4];
+        let mut expected = (&mut buf[..]).crc32();
+        expected.write_all(b"1234").unwrap();
+        assert_eq!(reader.checksum(), expected.checksum());
+        assert_eq!(reader.fill_buf().unwrap(), b"56789");
+        reader.consume(5);
+        assert_eq!(reader.fill_buf().unwrap(), b"");
+        assert_eq!(reader.checksum(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn crc32_verify_mismatch() {
+        let data = b"123456789\x00\x39\xF4\xCB";
+        let mut reader = (&data[..]).verify_crc32(Endianness::Little);
+        let len = reader.fill_buf().unwrap().len();
+        reader.consume(len);
+        assert!(matches!(reader.fill_buf(), Err(ChecksumReadError::ChecksumMismatch(_))));
+    }
+
+    #[test]
+    fn crc32_verify_missing_trailer() {
+        let mut reader = (&b"12"[..]).verify_crc32(Endianness::Little);
+        assert!(matches!(reader.fill_buf(), Err(ChecksumReadError::UnexpectedEnd(_))));
+    }
+}
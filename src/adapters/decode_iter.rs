@@ -0,0 +1,73 @@
+use crate::BufRead;
+
+/// Iterator decoding items from a reader - returned from [`BufRead::decode_iter`].
+pub struct DecodeIter<R, F> {
+    reader: R,
+    decoder: F,
+    done: bool,
+}
+
+impl<T, E, R: BufRead, F: FnMut(&mut R) -> Result<Option<T>, E>> DecodeIter<R, F> {
+    pub(crate) fn new(reader: R, decoder: F) -> Self {
+        DecodeIter {
+            reader,
+            decoder,
+            done: false,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<T, E, R: BufRead, F: FnMut(&mut R) -> Result<Option<T>, E>> Iterator for DecodeIter<R, F> {
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = (self.decoder)(&mut self.reader).transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.done = true;
+        }
+        result
+    }
+}
+
+impl<T, E, R: BufRead, F: FnMut(&mut R) -> Result<Option<T>, E>> core::iter::FusedIterator for DecodeIter<R, F> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+
+    #[test]
+    fn decode_pairs() {
+        let mut items = (&[1u8, 2, 3, 4, 5][..]).decode_iter(|reader| {
+            let first = match reader.read_byte().unwrap_or_else(|infallible| match infallible {}) {
+                Some(byte) => byte,
+                None => return Ok(None),
+            };
+            match reader.read_byte().unwrap_or_else(|infallible| match infallible {}) {
+                Some(second) => Ok(Some((first, second))),
+                None => Err(first),
+            }
+        });
+        assert_eq!(items.next(), Some(Ok((1, 2))));
+        assert_eq!(items.next(), Some(Ok((3, 4))));
+        assert_eq!(items.next(), Some(Err(5)));
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn decode_clean_end() {
+        let mut items = (&[1u8, 2][..]).decode_iter(|reader| reader.read_byte());
+        assert_eq!(items.next(), Some(Ok(1)));
+        assert_eq!(items.next(), Some(Ok(2)));
+        assert_eq!(items.next(), None);
+        assert_eq!(items.next(), None);
+    }
+}
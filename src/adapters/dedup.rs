@@ -0,0 +1,97 @@
+use crate::BufRead;
+
+const BUF_SIZE: usize = 256;
+
+/// Collapses runs of identical bytes - returned from [`BufRead::dedup`].
+pub struct DedupReader<R> {
+    reader: R,
+    keep: usize,
+    last: Option<u8>,
+    run: usize,
+    buf: [u8; BUF_SIZE],
+    pos: usize,
+    len: usize,
+}
+
+impl<R: BufRead> DedupReader<R> {
+    pub(crate) fn new(reader: R, keep: usize) -> Self {
+        assert!(keep > 0, "dedup must keep at least one byte of each run");
+        DedupReader {
+            reader,
+            keep,
+            last: None,
+            run: 0,
+            buf: [0; BUF_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that any data buffered in this adapter is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for DedupReader<R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.pos == self.len {
+            self.pos = 0;
+            self.len = 0;
+            while self.len == 0 {
+                let input = self.reader.fill_buf()?;
+                if input.is_empty() {
+                    break;
+                }
+                let mut processed = 0;
+                while processed < input.len() && self.len < BUF_SIZE {
+                    let byte = input[processed];
+                    processed += 1;
+                    if self.last == Some(byte) {
+                        self.run = self.run.saturating_add(1);
+                    } else {
+                        self.last = Some(byte);
+                        self.run = 1;
+                    }
+                    if self.run <= self.keep {
+                        self.buf[self.len] = byte;
+                        self.len += 1;
+                    }
+                }
+                self.reader.consume(processed);
+            }
+        }
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.len - self.pos);
+        self.pos += amount;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+
+    #[test]
+    fn dedup() {
+        let chunks: [&[u8]; 3] = [&[1, 1, 1], &[1, 2, 2], &[3, 3, 3, 1]];
+        let mut reader = crate::from_slices(chunks.iter().copied()).dedup(2);
+        let mut buf = [0; 7];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 1, 2, 2, 3, 3, 1]);
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+
+        let mut reader = (&[4, 4, 5, 5, 5, 4][..]).dedup(1);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [4, 5, 4]);
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+use crate::BufRead;
+use crate::error::{FrameError, UnexpectedEnd};
+use super::length_prefix::read_length;
+
+/// Reads frames tagged with channel ids - returned from [`BufRead::demux`].
+///
+/// Each frame consists of a one-byte channel id, LEB128-encoded length and the payload.
+pub struct Demux<R> {
+    reader: R,
+    max_frame_len: usize,
+}
+
+impl<R: BufRead> Demux<R> {
+    pub(crate) fn new(reader: R, max_frame_len: usize) -> Self {
+        Demux {
+            reader,
+            max_frame_len,
+        }
+    }
+
+    /// Reads the next frame returning its channel id and payload.
+    ///
+    /// Returns `Ok(None)` if the stream ended at a frame boundary.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameError::TooLong`] without reading the payload if the frame is longer than
+    /// `max_frame_len`. Truncated frames are reported as [`FrameError::UnexpectedEnd`].
+    #[allow(clippy::type_complexity)]
+    pub fn next_frame(&mut self) -> Result<Option<(u8, Vec<u8>)>, FrameError<R::ReadError>> {
+        let channel = match self.reader.read_byte().map_err(FrameError::ReadingFailed)? {
+            Some(channel) => channel,
+            None => return Ok(None),
+        };
+        let len = read_length(&mut self.reader)?.ok_or(UnexpectedEnd::new(2, 1))?;
+        if len > self.max_frame_len as u64 {
+            return Err(FrameError::TooLong(len));
+        }
+        let mut payload = Vec::new();
+        self.reader.read_exact_into_spare(&mut payload, len as usize)?;
+        Ok(Some((channel, payload)))
+    }
+
+    /// Returns the maximum allowed length of a frame payload.
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Sets the maximum allowed length of a frame payload.
+    pub fn set_max_frame_len(&mut self, max_frame_len: usize) {
+        self.max_frame_len = max_frame_len;
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+    use crate::error::FrameError;
+
+    #[test]
+    fn frames() {
+        let mut demux = (&[1, 2, 0xAA, 0xBB, 0, 0, 1, 1, 0xCC][..]).demux(2);
+        assert_eq!(demux.next_frame().unwrap(), Some((1, alloc::vec![0xAA, 0xBB])));
+        assert_eq!(demux.next_frame().unwrap(), Some((0, alloc::vec![])));
+        assert_eq!(demux.next_frame().unwrap(), Some((1, alloc::vec![0xCC])));
+        assert_eq!(demux.next_frame().unwrap(), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!((&[1, 3, 0, 0, 0][..]).demux(2).next_frame(), Err(FrameError::TooLong(3))));
+        assert!(matches!((&[1][..]).demux(2).next_frame(), Err(FrameError::UnexpectedEnd(_))));
+        assert!(matches!((&[1, 2, 0][..]).demux(2).next_frame(), Err(FrameError::UnexpectedEnd(_))));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::BufWrite;
+
+const CHUNK_SIZE: usize = 128;
+const MAX_ESCAPE_LEN: usize = 4;
+
+/// Escapes non-printable bytes - returned from [`BufWrite::escape_ascii`].
+pub struct EscapeAsciiWriter<W> {
+    writer: W,
+}
+
+impl<W: BufWrite> EscapeAsciiWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        EscapeAsciiWriter {
+            writer,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for EscapeAsciiWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut len = 0;
+        for &byte in bytes {
+            if len + MAX_ESCAPE_LEN > chunk.len() {
+                self.writer.write_all(&chunk[..len])?;
+                len = 0;
+            }
+            for escaped in core::ascii::escape_default(byte) {
+                chunk[len] = escaped;
+                len += 1;
+            }
+        }
+        self.writer.write_all(&chunk[..len])
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn escape() {
+        let mut buf = [0u8; 32];
+        let mut writer = (&mut buf[..]).escape_ascii();
+        writer.write_all(b"a\tb\n\"\\\x00\xff").unwrap();
+        let remaining = writer.into_inner().len();
+        assert_eq!(&buf[..(32 - remaining)], &b"a\\tb\\n\\\"\\\\\\x00\\xff"[..]);
+    }
+
+    #[test]
+    fn escape_long() {
+        let input = [0x7f; 100];
+        let mut buf = [0u8; 400];
+        (&mut buf[..]).escape_ascii().write_all(&input).unwrap();
+        assert!(buf.chunks(4).all(|chunk| chunk == b"\\x7f"));
+    }
+}
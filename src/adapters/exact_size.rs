@@ -0,0 +1,101 @@
+use crate::BufWrite;
+use crate::error::{BufferOverflow, ExactSizeError};
+
+/// Checks that exactly the expected number of bytes was written - returned from
+/// [`BufWrite::expect_exact`].
+pub struct ExactSizeWriter<W> {
+    writer: W,
+    expected: u64,
+    written: u64,
+}
+
+impl<W: BufWrite> ExactSizeWriter<W> {
+    pub(crate) fn new(writer: W, expected: u64) -> Self {
+        ExactSizeWriter {
+            writer,
+            expected,
+            written: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the number of bytes that still need to be written.
+    pub fn remaining(&self) -> u64 {
+        self.expected - self.written
+    }
+
+    /// Checks that all expected bytes were written and returns the underlying writer.
+    ///
+    /// The underlying writer is not flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExactSizeError::Short`] if fewer bytes than expected were written.
+    pub fn finish(self) -> Result<W, ExactSizeError<W::WriteError>> {
+        if self.written < self.expected {
+            return Err(ExactSizeError::Short(self.expected - self.written));
+        }
+        Ok(self.writer)
+    }
+
+    /// Returns the underlying writer without checking the number of written bytes.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for ExactSizeWriter<W> {
+    type WriteError = ExactSizeError<W::WriteError>;
+
+    /// Writes the bytes if they don't exceed the expected size.
+    ///
+    /// Nothing is written if the expected size would be exceeded.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let available = self.expected - self.written;
+        if bytes.len() as u64 > available {
+            let past_end = (bytes.len() as u64 - available) as usize;
+            return Err(BufferOverflow::new(past_end).into());
+        }
+        self.writer.write_all(bytes).map_err(ExactSizeError::WritingFailed)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush().map_err(ExactSizeError::WritingFailed)
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+    use crate::error::ExactSizeError;
+
+    #[test]
+    fn exact_size() {
+        let mut buf = [0u8; 8];
+        let mut writer = (&mut buf[..]).expect_exact(4);
+        writer.write_all(b"abc").unwrap();
+        assert!(matches!(writer.write_all(b"de"), Err(ExactSizeError::Overflow(_))));
+        assert_eq!(writer.remaining(), 1);
+        writer.write_all(b"d").unwrap();
+        assert!(writer.finish().is_ok());
+
+        let mut buf = [0u8; 8];
+        let mut writer = (&mut buf[..]).expect_exact(4);
+        writer.write_all(b"a").unwrap();
+        assert!(matches!(writer.finish(), Err(ExactSizeError::Short(3))));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::BufWrite;
+
+/// Flushes the writer periodically - returned from [`BufWrite::flush_every`].
+pub struct FlushEvery<W> {
+    writer: W,
+    threshold: usize,
+    unflushed: usize,
+}
+
+impl<W: BufWrite> FlushEvery<W> {
+    pub(crate) fn new(writer: W, threshold: usize) -> Self {
+        FlushEvery {
+            writer,
+            threshold,
+            unflushed: 0,
+        }
+    }
+
+    /// Returns the number of bytes written since the last flush.
+    pub fn unflushed(&self) -> usize {
+        self.unflushed
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for FlushEvery<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        self.unflushed = self.unflushed.saturating_add(bytes.len());
+        if self.unflushed >= self.threshold {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()?;
+        self.unflushed = 0;
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    struct Flushes(usize);
+
+    impl BufWrite for Flushes {
+        type WriteError = core::convert::Infallible;
+
+        fn write_all(&mut self, _bytes: &[u8]) -> Result<(), Self::WriteError> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::WriteError> {
+            self.0 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn threshold() {
+        let mut writer = Flushes(0).flush_every(4);
+        writer.write_all(b"abc").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.unflushed(), 3);
+        writer.write_all(b"d").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.unflushed(), 0);
+        writer.write_all(b"efghij").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.unflushed(), 0);
+        writer.write_all(b"k").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.unflushed(), 1);
+        assert_eq!(writer.into_inner().0, 2);
+    }
+
+    #[test]
+    fn explicit_flush_resets_counter() {
+        let mut writer = Flushes(0).flush_every(4);
+        writer.write_all(b"abc").unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.unflushed(), 0);
+        writer.write_all(b"def").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.unflushed(), 3);
+        assert_eq!(writer.into_inner().0, 1);
+    }
+}
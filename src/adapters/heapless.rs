@@ -0,0 +1,138 @@
+use crate::BufWrite;
+use crate::error::{BufferOverflow, StrWriteError};
+use super::utf8::PendingChar;
+
+/// Writes UTF-8 bytes into [`heapless::String`] validating them.
+///
+/// Multi-byte characters may be split across multiple writes - the incomplete character is kept
+/// in a small internal buffer until the rest arrives.
+pub struct HeaplessStrWriter<const N: usize> {
+    string: heapless::String<N>,
+    pending: PendingChar,
+}
+
+impl<const N: usize> HeaplessStrWriter<N> {
+    /// Creates the writer appending to `string`.
+    pub fn new(string: heapless::String<N>) -> Self {
+        HeaplessStrWriter {
+            string,
+            pending: PendingChar::new(),
+        }
+    }
+
+    /// Returns the string written so far excluding any incomplete character.
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    /// Returns the string checking that the last character is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StrWriteError::InvalidUtf8`] if the bytes written so far end with an incomplete
+    /// character.
+    pub fn finish(self) -> Result<heapless::String<N>, StrWriteError> {
+        self.pending.check_complete()?;
+        Ok(self.string)
+    }
+
+    /// Returns the string discarding any incomplete character.
+    pub fn into_inner(self) -> heapless::String<N> {
+        self.string
+    }
+
+    /// Appends the valid prefix of `bytes` keeping the incomplete character at the end.
+    fn push_valid(&mut self, bytes: &[u8]) -> Result<(), StrWriteError> {
+        match core::str::from_utf8(bytes) {
+            Ok(s) => push_str(&mut self.string, s),
+            Err(error) => {
+                let (valid, rest) = bytes.split_at(error.valid_up_to());
+                // SAFETY: from_utf8 checked the bytes up to valid_up_to
+                push_str(&mut self.string, unsafe { core::str::from_utf8_unchecked(valid) })?;
+                if error.error_len().is_some() {
+                    return Err(error.into());
+                }
+                self.pending.set(rest);
+                Ok(())
+            },
+        }
+    }
+}
+
+fn push_str<const N: usize>(string: &mut heapless::String<N>, s: &str) -> Result<(), StrWriteError> {
+    let available = string.capacity() - string.len();
+    if s.len() > available {
+        return Err(BufferOverflow::new(s.len() - available).into());
+    }
+    // can not fail because we've checked the capacity above
+    let _ = string.push_str(s);
+    Ok(())
+}
+
+impl<const N: usize> BufWrite for HeaplessStrWriter<N> {
+    type WriteError = StrWriteError;
+
+    /// Validates the bytes and appends them to the string.
+    ///
+    /// If the bytes are invalid the valid prefix is written and an error is returned. If a
+    /// character split across writes turns out to be invalid only that character is discarded,
+    /// the following bytes are written as usual and the error is returned.
+    fn write_all(&mut self, mut bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut result = Ok(());
+        if !self.pending.is_empty() {
+            let (consumed, complete) = self.pending.complete(bytes);
+            match complete {
+                Ok(Some(s)) => {
+                    push_str(&mut self.string, s)?;
+                    self.pending.clear();
+                },
+                Ok(None) => (),
+                Err(error) => result = Err(error.into()),
+            }
+            bytes = &bytes[consumed..];
+        }
+        let written = self.push_valid(bytes);
+        result.and(written)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+    use crate::error::StrWriteError;
+    use super::HeaplessStrWriter;
+
+    #[test]
+    fn split_characters() {
+        let mut writer = HeaplessStrWriter::new(heapless::String::<8>::new());
+        let bytes = "a€ř".as_bytes();
+        for byte in bytes {
+            writer.write_all(core::slice::from_ref(byte)).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), "a€ř");
+    }
+
+    #[test]
+    fn errors() {
+        let mut writer = HeaplessStrWriter::new(heapless::String::<4>::new());
+        writer.write_all(&"€".as_bytes()[..2]).unwrap();
+        assert!(matches!(writer.write_all(b"a"), Err(StrWriteError::InvalidUtf8(_))));
+        writer.write_all(b"b").unwrap();
+        assert_eq!(writer.finish().unwrap(), "ab");
+
+        let mut writer = HeaplessStrWriter::new(heapless::String::<4>::new());
+        writer.write_all(b"ab").unwrap();
+        assert!(matches!(writer.write_all(b"abc"), Err(StrWriteError::BufferOverflow(_))));
+        assert_eq!(writer.as_str(), "ab");
+        writer.write_all(&"€".as_bytes()[..1]).unwrap();
+        assert!(writer.finish().is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use core::fmt;
+use crate::BufRead;
+use super::consumed::ConsumedBytes;
+
+const LINE_LEN: usize = 16;
+
+/// Logs consumed bytes in hexdump format - returned from [`BufRead::hexdump`].
+///
+/// If the consumed bytes can not be retrieved from the underlying reader without IO and getting
+/// them fails, they are not logged and the error is returned from the next call to
+/// [`fill_buf`](BufRead::fill_buf).
+pub struct HexdumpReader<R: BufRead, F> {
+    reader: ConsumedBytes<R>,
+    f: F,
+    line: Line,
+}
+
+/// The line being currently assembled.
+struct Line {
+    bytes: [u8; LINE_LEN],
+    len: usize,
+    offset: u64,
+}
+
+impl Line {
+    fn push<F: FnMut(fmt::Arguments<'_>)>(&mut self, byte: u8, f: &mut F) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+        if self.len == LINE_LEN {
+            self.flush(f);
+        }
+    }
+
+    fn flush<F: FnMut(fmt::Arguments<'_>)>(&mut self, f: &mut F) {
+        if self.len > 0 {
+            let line = &self.bytes[..self.len];
+            f(format_args!("{:08x}: {}  {}", self.offset, Hex(line), Ascii(line)));
+            self.offset += self.len as u64;
+            self.len = 0;
+        }
+    }
+}
+
+impl<R: BufRead, F: FnMut(fmt::Arguments<'_>)> HexdumpReader<R, F> {
+    pub(crate) fn new(reader: R, f: F) -> Self {
+        HexdumpReader {
+            reader: ConsumedBytes::new(reader),
+            f,
+            line: Line {
+                bytes: [0; LINE_LEN],
+                len: 0,
+                offset: 0,
+            },
+        }
+    }
+
+    /// Logs the incomplete line, if any.
+    ///
+    /// This is done automatically at the end of the stream.
+    pub fn flush_line(&mut self) {
+        self.line.flush(&mut self.f)
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that the incomplete line is not logged, call [`flush_line`](Self::flush_line) first
+    /// if you want to log it.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+/// Formats bytes as hex in groups of two, padded to the width of the whole line.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..LINE_LEN {
+            if i > 0 && i % 2 == 0 {
+                f.write_str(" ")?;
+            }
+            match self.0.get(i) {
+                Some(byte) => write!(f, "{:02x}", byte)?,
+                None => f.write_str("  ")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats printable ASCII bytes as is and other bytes as dots.
+struct Ascii<'a>(&'a [u8]);
+
+impl fmt::Display for Ascii<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        for &byte in self.0 {
+            let c = if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' };
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead, F: FnMut(fmt::Arguments<'_>)> BufRead for HexdumpReader<R, F> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        let buf = self.reader.fill_buf()?;
+        if buf.is_empty() {
+            self.line.flush(&mut self.f);
+        }
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        let line = &mut self.line;
+        let f = &mut self.f;
+        self.reader.consume_with(amount, |bytes| {
+            for &byte in bytes {
+                line.push(byte, f);
+            }
+        })
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.get_ref().buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.get_ref().size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.get_ref().stream_position_hint()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use crate::BufRead;
+
+    #[test]
+    fn hexdump() {
+        let mut lines = Vec::new();
+        let mut reader = (&b"Hello world, this is lgio!\n"[..]).hexdump(|line| lines.push(line.to_string()));
+        while reader.read_byte().unwrap_or_else(|infallible| match infallible {}).is_some() {}
+        assert_eq!(lines, [
+            "00000000: 4865 6c6c 6f20 776f 726c 642c 2074 6869  Hello world, thi",
+            "00000010: 7320 6973 206c 6769 6f21 0a              s is lgio!.",
+        ]);
+    }
+}
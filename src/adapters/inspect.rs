@@ -0,0 +1,78 @@
+use crate::BufRead;
+use super::consumed::ConsumedBytes;
+
+/// Calls a closure with consumed bytes - returned from [`BufRead::inspect`].
+///
+/// The closure is called from `consume` with exactly the consumed bytes, it's never called from
+/// `fill_buf`. Thus repeated calls to `fill_buf` without consuming don't cause the same bytes to
+/// be observed multiple times and each byte is passed to the closure exactly once.
+///
+/// If the consumed bytes can not be retrieved from the underlying reader without IO and getting
+/// them fails, the closure is not called and the error is returned from the next call to
+/// [`fill_buf`](BufRead::fill_buf).
+pub struct Inspect<R: BufRead, F> {
+    reader: ConsumedBytes<R>,
+    f: F,
+}
+
+impl<R: BufRead, F: FnMut(&[u8])> Inspect<R, F> {
+    pub(crate) fn new(reader: R, f: F) -> Self {
+        Inspect {
+            reader: ConsumedBytes::new(reader),
+            f,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: BufRead, F: FnMut(&[u8])> BufRead for Inspect<R, F> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume_with(amount, &mut self.f)
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.get_ref().buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.get_ref().size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.get_ref().stream_position_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+
+    #[test]
+    fn inspect() {
+        let chunks: [&[u8]; 2] = [&[1, 2, 3], &[4, 5]];
+        let mut sum = 0u32;
+        let mut calls = 0;
+        let mut reader = crate::from_slices(chunks.iter().copied()).inspect(|bytes| {
+            sum += bytes.iter().map(|&byte| u32::from(byte)).sum::<u32>();
+            calls += 1;
+        });
+        reader.fill_buf().unwrap_or_else(|infallible| match infallible {});
+        reader.fill_buf().unwrap_or_else(|infallible| match infallible {});
+        reader.consume(1);
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+        assert_eq!(sum, 15);
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{BufRead, BufWrite};
+use crate::error::{JsonUnescapeError, UnexpectedEnd};
+
+const CHUNK_SIZE: usize = 128;
+const MAX_ESCAPE_LEN: usize = 6;
+const BUF_SIZE: usize = 256;
+
+/// Writes the JSON escape sequence of `byte` into `out`, returns the number of bytes written.
+fn json_escape(byte: u8, out: &mut [u8; MAX_ESCAPE_LEN]) -> usize {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let short = match byte {
+        b'"' => b'"',
+        b'\\' => b'\\',
+        0x08 => b'b',
+        0x0C => b'f',
+        b'\n' => b'n',
+        b'\r' => b'r',
+        b'\t' => b't',
+        0x00..=0x1F => {
+            *out = [b'\\', b'u', b'0', b'0', HEX[usize::from(byte >> 4)], HEX[usize::from(byte & 0x0F)]];
+            return MAX_ESCAPE_LEN;
+        },
+        _ => {
+            out[0] = byte;
+            return 1;
+        },
+    };
+    out[0] = b'\\';
+    out[1] = short;
+    2
+}
+
+/// Escapes bytes for use inside JSON strings - returned from [`BufWrite::json_string_escape`].
+pub struct JsonStringEscapeWriter<W> {
+    writer: W,
+}
+
+impl<W: BufWrite> JsonStringEscapeWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        JsonStringEscapeWriter {
+            writer,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for JsonStringEscapeWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut len = 0;
+        for &byte in bytes {
+            if len + MAX_ESCAPE_LEN > chunk.len() {
+                self.writer.write_all(&chunk[..len])?;
+                len = 0;
+            }
+            let mut escaped = [0u8; MAX_ESCAPE_LEN];
+            let escaped_len = json_escape(byte, &mut escaped);
+            chunk[len..(len + escaped_len)].copy_from_slice(&escaped[..escaped_len]);
+            len += escaped_len;
+        }
+        self.writer.write_all(&chunk[..len])
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+/// Un-escapes the contents of JSON string - returned from [`BufRead::json_string_unescape`].
+///
+/// The reader stops at the first unescaped `"` which is left in the underlying reader.
+pub struct JsonStringUnescapeReader<R> {
+    reader: R,
+    reached_quote: bool,
+    buf: [u8; BUF_SIZE],
+    pos: usize,
+    len: usize,
+}
+
+impl<R: BufRead> JsonStringUnescapeReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        JsonStringUnescapeReader {
+            reader,
+            reached_quote: false,
+            buf: [0; BUF_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the closing quote was found.
+    ///
+    /// This can be used to distinguish the end of the string from the end of the underlying
+    /// stream after `fill_buf` returned an empty slice.
+    pub fn reached_quote(&self) -> bool {
+        self.reached_quote
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that any data buffered in this adapter is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_escaped_byte(&mut self, required: usize, read: usize) -> Result<u8, JsonUnescapeError<R::ReadError>> {
+        self.reader.read_byte()
+            .map_err(JsonUnescapeError::ReadingFailed)?
+            .ok_or_else(|| UnexpectedEnd::new(required, read).into())
+    }
+
+    /// Reads the four hex digits following `\u`.
+    fn read_hex4(&mut self) -> Result<u16, JsonUnescapeError<R::ReadError>> {
+        let mut value = 0u16;
+        for i in 0..4 {
+            let byte = self.read_escaped_byte(6, 2 + i)?;
+            let digit = (byte as char).to_digit(16).ok_or(JsonUnescapeError::InvalidUnicodeEscape)?;
+            value = value << 4 | digit as u16;
+        }
+        Ok(value)
+    }
+
+    /// Un-escapes the sequence following `\` into the internal buffer.
+    fn unescape(&mut self) -> Result<(), JsonUnescapeError<R::ReadError>> {
+        let byte = match self.read_escaped_byte(2, 1)? {
+            b'"' => b'"',
+            b'\\' => b'\\',
+            b'/' => b'/',
+            b'b' => 0x08,
+            b'f' => 0x0C,
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'u' => {
+                let code_point = match self.read_hex4()? {
+                    high @ 0xD800..=0xDBFF => {
+                        if self.read_escaped_byte(2, 0)? != b'\\' || self.read_escaped_byte(2, 1)? != b'u' {
+                            return Err(JsonUnescapeError::LoneSurrogate(high));
+                        }
+                        match self.read_hex4()? {
+                            low @ 0xDC00..=0xDFFF => 0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(low) - 0xDC00),
+                            _ => return Err(JsonUnescapeError::LoneSurrogate(high)),
+                        }
+                    },
+                    low @ 0xDC00..=0xDFFF => return Err(JsonUnescapeError::LoneSurrogate(low)),
+                    code_point => u32::from(code_point),
+                };
+                // surrogates were handled above so the code point is always valid
+                let c = char::from_u32(code_point).expect("invalid code point");
+                self.len += c.encode_utf8(&mut self.buf[self.len..]).len();
+                return Ok(());
+            },
+            other => return Err(JsonUnescapeError::InvalidEscape(other)),
+        };
+        self.buf[self.len] = byte;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> BufRead for JsonStringUnescapeReader<R> {
+    type ReadError = JsonUnescapeError<R::ReadError>;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.pos == self.len {
+            self.pos = 0;
+            self.len = 0;
+            while !self.reached_quote && self.len == 0 {
+                let input = self.reader.fill_buf().map_err(JsonUnescapeError::ReadingFailed)?;
+                match input.first() {
+                    None => break,
+                    Some(b'"') => self.reached_quote = true,
+                    Some(b'\\') => {
+                        self.reader.consume(1);
+                        self.unescape()?;
+                    },
+                    Some(_) => {
+                        let plain = input.iter().position(|&byte| byte == b'"' || byte == b'\\').unwrap_or(input.len());
+                        let len = plain.min(BUF_SIZE);
+                        self.buf[..len].copy_from_slice(&input[..len]);
+                        self.len = len;
+                        self.reader.consume(len);
+                    },
+                }
+            }
+        }
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.len - self.pos);
+        self.pos += amount;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+    use crate::error::JsonUnescapeError;
+
+    #[test]
+    fn escape() {
+        let mut buf = [0u8; 64];
+        let mut writer = (&mut buf[..]).json_string_escape();
+        writer.write_all("a\"b\\c\n\t\x01\x1fž/".as_bytes()).unwrap();
+        let remaining = writer.into_inner().len();
+        assert_eq!(&buf[..(64 - remaining)], "a\\\"b\\\\c\\n\\t\\u0001\\u001fž/".as_bytes());
+    }
+
+    fn unescape_all(input: &[u8], output: &mut [u8]) -> Result<usize, JsonUnescapeError<core::convert::Infallible>> {
+        let mut reader = input.json_string_unescape();
+        let mut len = 0;
+        while let Some(byte) = reader.read_byte()? {
+            output[len] = byte;
+            len += 1;
+        }
+        assert!(reader.reached_quote());
+        Ok(len)
+    }
+
+    #[test]
+    fn unescape() {
+        let mut buf = [0u8; 64];
+        let input = br#"a\"b\\c\n\/\u0001\u017e\ud83d\ude00x"rest"#;
+        let len = unescape_all(input, &mut buf).unwrap();
+        assert_eq!(&buf[..len], "a\"b\\c\n/\x01\u{17e}\u{1f600}x".as_bytes());
+
+        let chunks: [&[u8]; 3] = [br#"ab\u0"#, br#"17"#, br#"e""#];
+        let mut reader = crate::from_slices(chunks.iter().copied()).json_string_unescape();
+        let mut output = [0u8; 4];
+        reader.read_exact(&mut output).unwrap();
+        assert_eq!(output, "abž".as_bytes());
+        assert!(reader.fill_buf().unwrap().is_empty());
+        assert!(reader.reached_quote());
+    }
+
+    #[test]
+    fn unescape_errors() {
+        let mut buf = [0u8; 16];
+        assert!(matches!(unescape_all(br#"\x""#, &mut buf), Err(JsonUnescapeError::InvalidEscape(b'x'))));
+        assert!(matches!(unescape_all(br#"\u12g4""#, &mut buf), Err(JsonUnescapeError::InvalidUnicodeEscape)));
+        assert!(matches!(unescape_all(br#"\ud83dx""#, &mut buf), Err(JsonUnescapeError::LoneSurrogate(0xD83D))));
+        assert!(matches!(unescape_all(br#"\ude00""#, &mut buf), Err(JsonUnescapeError::LoneSurrogate(0xDE00))));
+        assert!(matches!(unescape_all(br#"\u12"#, &mut buf), Err(JsonUnescapeError::UnexpectedEnd(_))));
+    }
+}
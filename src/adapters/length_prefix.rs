@@ -0,0 +1,177 @@
+use core::convert::TryFrom;
+use crate::{BufRead, BufWrite};
+use crate::error::{LengthPrefixError, UnexpectedEnd};
+
+const MAX_VARINT_LEN: usize = 10;
+
+/// Reads LEB128-encoded length, returns `None` if the reader ended before the first byte.
+pub(crate) fn read_length<R: BufRead + ?Sized>(reader: &mut R) -> Result<Option<u64>, LengthPrefixError<R::ReadError>> {
+    let mut len = 0u64;
+    for i in 0..MAX_VARINT_LEN {
+        let byte = match reader.read_byte().map_err(LengthPrefixError::ReadingFailed)? {
+            Some(byte) => byte,
+            None if i == 0 => return Ok(None),
+            None => return Err(UnexpectedEnd::new(i + 1, i).into()),
+        };
+        let shift = i * 7;
+        let value = u64::from(byte & 0x7F);
+        if shift == 63 && value > 1 {
+            return Err(LengthPrefixError::InvalidLength);
+        }
+        len |= value << shift;
+        if byte & 0x80 == 0 {
+            return Ok(Some(len));
+        }
+    }
+    Err(LengthPrefixError::InvalidLength)
+}
+
+/// Prefixes each write with its length - returned from [`BufWrite::length_prefixed_write`].
+///
+/// The length is encoded as unsigned LEB128 varint.
+pub struct LengthPrefixWriter<W> {
+    writer: W,
+}
+
+impl<W: BufWrite> LengthPrefixWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        LengthPrefixWriter {
+            writer,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for LengthPrefixWriter<W> {
+    type WriteError = W::WriteError;
+
+    /// Writes `bytes` as a single frame.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut prefix = [0u8; MAX_VARINT_LEN];
+        let mut len = bytes.len() as u64;
+        let mut prefix_len = 0;
+        loop {
+            let byte = (len & 0x7F) as u8;
+            len >>= 7;
+            if len == 0 {
+                prefix[prefix_len] = byte;
+                prefix_len += 1;
+                break;
+            }
+            prefix[prefix_len] = byte | 0x80;
+            prefix_len += 1;
+        }
+        self.writer.reserve(prefix_len + bytes.len());
+        self.writer.write_all(&prefix[..prefix_len])?;
+        self.writer.write_all(bytes)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+/// Reads the contents of length-prefixed frames - returned from [`BufRead::length_prefixed`].
+///
+/// The length prefixes are stripped so the contents of consecutive frames appear concatenated.
+pub struct LengthPrefixReader<R> {
+    reader: R,
+    frame_len: u64,
+    remaining: u64,
+}
+
+impl<R: BufRead> LengthPrefixReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        LengthPrefixReader {
+            reader,
+            frame_len: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Returns the number of bytes remaining in the current frame.
+    pub fn frame_remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for LengthPrefixReader<R> {
+    type ReadError = LengthPrefixError<R::ReadError>;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        while self.remaining == 0 {
+            match read_length(&mut self.reader)? {
+                Some(len) => {
+                    self.frame_len = len;
+                    self.remaining = len;
+                },
+                None => return Ok(&[]),
+            }
+        }
+        let buf = self.reader.fill_buf().map_err(LengthPrefixError::ReadingFailed)?;
+        if buf.is_empty() {
+            let total_required = usize::try_from(self.frame_len).unwrap_or(usize::MAX);
+            let available = usize::try_from(self.frame_len - self.remaining).unwrap_or(usize::MAX);
+            return Err(UnexpectedEnd::new(total_required, available).into());
+        }
+        let len = usize::try_from(self.remaining).map_or(buf.len(), |remaining| remaining.min(buf.len()));
+        Ok(&buf[..len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount as u64 <= self.remaining);
+        self.reader.consume(amount);
+        self.remaining -= amount as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+    use crate::error::LengthPrefixError;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = [0u8; 140];
+        let mut writer = (&mut buf[..]).length_prefixed_write();
+        writer.write_all(&[1, 2, 3]).unwrap();
+        writer.write_all(&[]).unwrap();
+        writer.write_all(&[7; 130]).unwrap();
+        let remaining = writer.into_inner().len();
+        assert_eq!(remaining, 140 - 4 - 1 - 132);
+        assert_eq!(buf[..6], [3, 1, 2, 3, 0, 0x82]);
+        assert_eq!(buf[6], 0x01);
+
+        let mut reader = (&buf[..(140 - remaining)]).length_prefixed();
+        let mut data = [0u8; 133];
+        reader.read_exact(&mut data).unwrap();
+        assert_eq!(data[..4], [1, 2, 3, 7]);
+        assert!(reader.fill_buf().unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut reader = (&[3, 1][..]).length_prefixed();
+        assert_eq!(reader.read_byte().unwrap(), Some(1));
+        assert!(matches!(reader.fill_buf(), Err(LengthPrefixError::UnexpectedEnd(_))));
+        let mut reader = (&[0x80][..]).length_prefixed();
+        assert!(matches!(reader.fill_buf(), Err(LengthPrefixError::UnexpectedEnd(_))));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::BufWrite;
+use crate::error::{LineLengthError, LineTooLong};
+
+/// Rejects lines longer than a limit - returned from [`BufWrite::max_line_len`].
+pub struct LineLengthLimit<W> {
+    writer: W,
+    max: usize,
+    current: usize,
+}
+
+impl<W: BufWrite> LineLengthLimit<W> {
+    pub(crate) fn new(writer: W, max: usize) -> Self {
+        LineLengthLimit {
+            writer,
+            max,
+            current: 0,
+        }
+    }
+
+    /// Returns the length of the current (unterminated) line.
+    pub fn current_line_len(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for LineLengthLimit<W> {
+    type WriteError = LineLengthError<W::WriteError>;
+
+    /// Writes the bytes if none of the lines exceeds the limit.
+    ///
+    /// The bytes are checked before writing so nothing is written if a line is too long.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut current = self.current;
+        for &byte in bytes {
+            if byte == b'\n' {
+                current = 0;
+            } else {
+                current += 1;
+                if current > self.max {
+                    return Err(LineTooLong::new(self.max).into());
+                }
+            }
+        }
+        self.writer.write_all(bytes).map_err(LineLengthError::WritingFailed)?;
+        self.current = current;
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush().map_err(LineLengthError::WritingFailed)
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+    use crate::error::LineLengthError;
+
+    #[test]
+    fn limit() {
+        let mut buf = [0u8; 16];
+        let mut writer = (&mut buf[..]).max_line_len(3);
+        writer.write_all(b"ab").unwrap();
+        writer.write_all(b"c\nabc").unwrap();
+        assert_eq!(writer.current_line_len(), 3);
+        assert!(matches!(writer.write_all(b"d\n"), Err(LineLengthError::LineTooLong(_))));
+        writer.write_all(b"\n\n").unwrap();
+        assert!(matches!(writer.write_all(b"abcd"), Err(LineLengthError::LineTooLong(_))));
+        assert_eq!(writer.into_inner().len(), 16 - 9);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{BufRead, BufWrite};
+use crate::error::WriteAllError;
 
 /// Converts reader errors using closure `F` - returned from [`BufRead::map_read_err`].
 pub struct MapReadErr<R, F> {
@@ -49,9 +50,25 @@ impl<E, W: BufWrite, F: FnMut(W::WriteError) -> E> BufWrite for MapWriteErr<W, F
         self.writer.write_all(bytes).map_err(&mut self.mapper)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        self.writer.write_all_counting(bytes).map_err(|error| error.map_err(&mut self.mapper))
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        self.writer.write_some(bytes).map_err(&mut self.mapper)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         self.writer.flush().map_err(&mut self.mapper)
     }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
 }
 
 /// Converts IO errors using closure `F` - returned from [`BufRead::map_err`].
@@ -88,9 +105,25 @@ impl<E, W: BufWrite, F: FnMut(W::WriteError) -> E> BufWrite for MapErr<W, F> {
         self.io.write_all(bytes).map_err(&mut self.mapper)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        self.io.write_all_counting(bytes).map_err(|error| error.map_err(&mut self.mapper))
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        self.io.write_some(bytes).map_err(&mut self.mapper)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.io.reserve(additional)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         self.io.flush().map_err(&mut self.mapper)
     }
+
+    fn needs_flush(&self) -> bool {
+        self.io.needs_flush()
+    }
 }
 
 /// Converts IO errors using [`Into::into`] - returned from [`BufRead::unify_err`].
@@ -127,7 +160,81 @@ impl<Io, E> BufWrite for UnifyErr<Io, E> where Io: BufRead + BufWrite, Io::ReadE
         self.io.write_all(bytes).map_err(Into::into)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        self.io.write_all_counting(bytes).map_err(|error| error.map_err(Into::into))
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        self.io.write_some(bytes).map_err(Into::into)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.io.reserve(additional)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         self.io.flush().map_err(Into::into)
     }
+
+    fn needs_flush(&self) -> bool {
+        self.io.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+    use crate::error::BufferOverflow;
+
+    /// Fixed-size loopback buffer using the same error for reading and writing.
+    struct Loopback {
+        buf: [u8; 4],
+        len: usize,
+    }
+
+    impl BufRead for Loopback {
+        type ReadError = BufferOverflow;
+
+        fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+            Ok(&self.buf[..self.len])
+        }
+
+        fn consume(&mut self, amount: usize) {
+            self.buf.copy_within(amount..self.len, 0);
+            self.len -= amount;
+        }
+    }
+
+    impl BufWrite for Loopback {
+        type WriteError = BufferOverflow;
+
+        fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+            (&mut self.buf[self.len..]).write_all(bytes)?;
+            self.len += bytes.len();
+            Ok(())
+        }
+
+        fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+            let len = bytes.len().min(self.buf.len() - self.len);
+            self.write_all(&bytes[..len])?;
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::WriteError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_some_forwarded() {
+        let mut io = Loopback { buf: [0; 4], len: 0 }.map_err(drop);
+        assert_eq!(io.write_some(&[1, 2, 3, 4, 5]), Ok(4));
+        assert_eq!(io.fill_buf(), Ok(&[1, 2, 3, 4][..]));
+
+        let mut buf = [0u8; 2];
+        let mut writer = (&mut buf[..]).map_write_err(drop);
+        assert_eq!(writer.write_some(&[1, 2, 3]), Ok(2));
+        assert_eq!(writer.write_some(&[3]), Ok(0));
+        assert_eq!(buf, [1, 2]);
+    }
 }
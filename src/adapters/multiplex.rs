@@ -0,0 +1,69 @@
+use crate::BufWrite;
+use crate::error::MultiplexError;
+
+/// Writes the same bytes into multiple writers - returned from [`crate::multiplex`].
+pub struct Multiplex<'a, W> {
+    writers: &'a mut [W],
+}
+
+impl<'a, W: BufWrite> Multiplex<'a, W> {
+    pub(crate) fn new(writers: &'a mut [W]) -> Self {
+        Multiplex {
+            writers,
+        }
+    }
+
+    /// Returns the underlying writers.
+    pub fn into_inner(self) -> &'a mut [W] {
+        self.writers
+    }
+}
+
+impl<W: BufWrite> BufWrite for Multiplex<'_, W> {
+    type WriteError = MultiplexError<W::WriteError>;
+
+    /// Writes the bytes into all writers in order.
+    ///
+    /// Stops at the first failing writer so the writers after it don't receive the bytes.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        for (index, writer) in self.writers.iter_mut().enumerate() {
+            writer.write_all(bytes).map_err(|error| MultiplexError::new(index, error))?;
+        }
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        for writer in &mut *self.writers {
+            writer.reserve(additional);
+        }
+    }
+
+    /// Flushes all writers in order, stopping at the first failing one.
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        for (index, writer) in self.writers.iter_mut().enumerate() {
+            writer.flush().map_err(|error| MultiplexError::new(index, error))?;
+        }
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writers.iter().any(BufWrite::needs_flush)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn multiplex() {
+        let mut first = [0u8; 4];
+        let mut second = [0u8; 2];
+        let mut writers = [&mut first[..], &mut second[..]];
+        let mut writer = crate::multiplex(&mut writers);
+        writer.write_all(&[1, 2]).unwrap();
+        assert_eq!(writer.write_all(&[3]).unwrap_err().index(), 1);
+        assert_eq!(first, [1, 2, 3, 0]);
+        assert_eq!(second, [1, 2]);
+    }
+}
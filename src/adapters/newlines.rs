@@ -0,0 +1,231 @@
+use crate::{BufRead, BufWrite};
+use super::consumed::filled_prefix;
+
+const BUF_SIZE: usize = 256;
+
+/// Specifies what to do with `\r` that is not followed by `\n`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LoneCr {
+    /// Keep lone `\r` as is.
+    Keep,
+    /// Convert lone `\r` to `\n` (old Mac line endings).
+    ToLf,
+}
+
+fn lone_cr_replacement(lone_cr: LoneCr) -> u8 {
+    match lone_cr {
+        LoneCr::Keep => b'\r',
+        LoneCr::ToLf => b'\n',
+    }
+}
+
+/// Converts `\r\n` to `\n` - returned from [`BufRead::normalize_newlines`].
+pub struct NormalizeNewlinesReader<R> {
+    reader: R,
+    lone_cr: LoneCr,
+    pending_cr: bool,
+    buf: [u8; BUF_SIZE],
+    pos: usize,
+    len: usize,
+}
+
+impl<R: BufRead> NormalizeNewlinesReader<R> {
+    pub(crate) fn new(reader: R, lone_cr: LoneCr) -> Self {
+        NormalizeNewlinesReader {
+            reader,
+            lone_cr,
+            pending_cr: false,
+            buf: [0; BUF_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that any data buffered in this adapter is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Fills the internal buffer, returns the length of the prefix of the inner buffer that can be
+    /// returned as-is if the internal buffer is empty.
+    fn fill_internal(&mut self) -> Result<usize, R::ReadError> {
+        self.pos = 0;
+        self.len = 0;
+        while self.len == 0 {
+            let input = self.reader.fill_buf()?;
+            if input.is_empty() {
+                if self.pending_cr {
+                    self.pending_cr = false;
+                    self.buf[0] = lone_cr_replacement(self.lone_cr);
+                    self.len = 1;
+                }
+                break;
+            }
+            if !self.pending_cr {
+                let passthrough = input.iter().position(|&byte| byte == b'\r').unwrap_or(input.len());
+                if passthrough > 0 {
+                    return Ok(passthrough);
+                }
+            }
+
+            let mut processed = 0;
+            // every input byte produces at most two output bytes
+            while processed < input.len() && self.len + 2 <= BUF_SIZE {
+                let byte = input[processed];
+                processed += 1;
+                if self.pending_cr {
+                    self.pending_cr = false;
+                    if byte == b'\n' {
+                        self.buf[self.len] = b'\n';
+                        self.len += 1;
+                        continue;
+                    }
+                    self.buf[self.len] = lone_cr_replacement(self.lone_cr);
+                    self.len += 1;
+                }
+                if byte == b'\r' {
+                    self.pending_cr = true;
+                } else {
+                    self.buf[self.len] = byte;
+                    self.len += 1;
+                }
+            }
+            self.reader.consume(processed);
+        }
+        Ok(0)
+    }
+}
+
+impl<R: BufRead> BufRead for NormalizeNewlinesReader<R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.pos < self.len {
+            return Ok(&self.buf[self.pos..self.len]);
+        }
+        let passthrough = self.fill_internal()?;
+        if passthrough > 0 {
+            filled_prefix(&mut self.reader, passthrough)
+        } else {
+            Ok(&self.buf[self.pos..self.len])
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if self.pos < self.len {
+            assert!(amount <= self.len - self.pos);
+            self.pos += amount;
+        } else {
+            self.reader.consume(amount);
+        }
+    }
+}
+
+/// Converts `\n` to `\r\n` - returned from [`BufWrite::crlf`].
+///
+/// `\n` already preceded by `\r` is left intact, even if they were written by separate calls.
+pub struct CrlfWriter<W> {
+    writer: W,
+    last_cr: bool,
+}
+
+impl<W: BufWrite> CrlfWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        CrlfWriter {
+            writer,
+            last_cr: false,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for CrlfWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut chunk = [0u8; BUF_SIZE];
+        let mut len = 0;
+        // Stored only after the chunk ending with the byte is written.
+        let mut last_cr = self.last_cr;
+        for &byte in bytes {
+            if len + 2 > chunk.len() {
+                self.writer.write_all(&chunk[..len])?;
+                self.last_cr = last_cr;
+                len = 0;
+            }
+            if byte == b'\n' && !last_cr {
+                chunk[len] = b'\r';
+                len += 1;
+            }
+            chunk[len] = byte;
+            len += 1;
+            last_cr = byte == b'\r';
+        }
+        self.writer.write_all(&chunk[..len])?;
+        self.last_cr = last_cr;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoneCr;
+    use crate::{BufRead, BufWrite};
+
+    fn normalize(chunks: &[&[u8]], lone_cr: LoneCr, expected: &[u8]) {
+        let mut reader = crate::from_slices(chunks.iter().copied()).normalize_newlines(lone_cr);
+        for &expected in expected {
+            assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(expected));
+        }
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+    }
+
+    #[test]
+    fn crlf() {
+        normalize(&[b"a\r\nb\r\n"], LoneCr::Keep, b"a\nb\n");
+    }
+
+    #[test]
+    fn crlf_split() {
+        normalize(&[b"a\r", b"\nb\r", b"\r\n", b"\r"], LoneCr::Keep, b"a\nb\r\n\r");
+    }
+
+    #[test]
+    fn lone_cr_to_lf() {
+        normalize(&[b"a\rb\r", b"\r\n", b"\r"], LoneCr::ToLf, b"a\nb\n\n\n");
+    }
+
+    #[test]
+    fn lf_to_crlf() {
+        let mut buf = [0u8; 16];
+        let mut writer = (&mut buf[..]).crlf();
+        writer.write_all(b"a\nb\r").unwrap();
+        writer.write_all(b"\n\n").unwrap();
+        let remaining = writer.into_inner().len();
+        assert_eq!(&buf[..(16 - remaining)], b"a\r\nb\r\n\r\n");
+    }
+
+    #[test]
+    fn lf_to_crlf_retry_after_failure() {
+        let mut buf = [0u8; 3];
+        let mut writer = (&mut buf[..]).crlf();
+        writer.write_all(b"a").unwrap();
+        assert!(writer.write_all(b"bc\r").is_err());
+        writer.write_all(b"\n").unwrap();
+        assert_eq!(buf, *b"a\r\n");
+    }
+}
@@ -0,0 +1,64 @@
+use crate::BufWrite;
+
+/// Calls a pacing hook before each write - returned from [`BufWrite::pace`].
+pub struct PacedWriter<W, F> {
+    writer: W,
+    pace: F,
+}
+
+impl<W: BufWrite, F: FnMut(usize)> PacedWriter<W, F> {
+    pub(crate) fn new(writer: W, pace: F) -> Self {
+        PacedWriter {
+            writer,
+            pace,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite, F: FnMut(usize)> BufWrite for PacedWriter<W, F> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        (self.pace)(bytes.len());
+        self.writer.write_all(bytes)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn pace() {
+        let mut calls = [0; 3];
+        let mut call_count = 0;
+        let mut buf = [0u8; 8];
+        let mut writer = (&mut buf[..]).pace(|len| {
+            calls[call_count] = len;
+            call_count += 1;
+        });
+        writer.write_all(b"abc").unwrap();
+        writer.write_all(b"de").unwrap();
+        writer.flush().unwrap();
+        writer.write_all(b"").unwrap();
+        assert_eq!(writer.into_inner().len(), 3);
+        assert_eq!(calls, [3, 2, 0]);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::BufWrite;
+use crate::error::{BufferOverflow, PadToError};
+
+/// Pads the output to a fixed length - returned from [`BufWrite::pad_to`].
+pub struct PadToWriter<W> {
+    writer: W,
+    total: u64,
+    written: u64,
+    fill: u8,
+}
+
+impl<W: BufWrite> PadToWriter<W> {
+    pub(crate) fn new(writer: W, total: u64, fill: u8) -> Self {
+        PadToWriter {
+            writer,
+            total,
+            written: 0,
+            fill,
+        }
+    }
+
+    /// Returns the number of bytes written so far, excluding padding.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Writes the padding up to the total length and returns the underlying writer.
+    ///
+    /// The underlying writer is not flushed.
+    pub fn finish(mut self) -> Result<W, W::WriteError> {
+        let chunk = [self.fill; 64];
+        let mut remaining = self.total - self.written;
+        while remaining > 0 {
+            let len = if remaining < chunk.len() as u64 { remaining as usize } else { chunk.len() };
+            self.writer.write_all(&chunk[..len])?;
+            remaining -= len as u64;
+        }
+        Ok(self.writer)
+    }
+}
+
+impl<W: BufWrite> BufWrite for PadToWriter<W> {
+    type WriteError = PadToError<W::WriteError>;
+
+    /// Writes the bytes if they fit into the total length.
+    ///
+    /// Nothing is written if the total length would be exceeded.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let available = self.total - self.written;
+        if bytes.len() as u64 > available {
+            let past_end = (bytes.len() as u64 - available) as usize;
+            return Err(BufferOverflow::new(past_end).into());
+        }
+        self.writer.write_all(bytes).map_err(PadToError::WritingFailed)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush().map_err(PadToError::WritingFailed)
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+    use crate::error::PadToError;
+
+    #[test]
+    fn pad_to() {
+        let mut buf = [0u8; 100];
+        let mut writer = (&mut buf[..]).pad_to(70, b' ');
+        writer.write_all(b"hello").unwrap();
+        assert!(matches!(writer.write_all(&[0; 66]), Err(PadToError::TooLong(_))));
+        assert_eq!(writer.written(), 5);
+        assert_eq!(writer.finish().unwrap().len(), 30);
+        assert_eq!(buf[..5], *b"hello");
+        assert!(buf[5..70].iter().all(|&byte| byte == b' '));
+        assert_eq!(buf[70], 0);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::BufRead;
+
+/// Tracks the number of consumed bytes - returned from [`BufRead::with_position`].
+pub struct PositionReader<R> {
+    reader: R,
+    position: u64,
+}
+
+impl<R: BufRead> PositionReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        PositionReader {
+            reader,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bytes consumed since the adapter was created.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for PositionReader<R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount);
+        self.position += amount as u64;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        Some(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+
+    #[test]
+    fn position() {
+        let chunks: [&[u8]; 2] = [&[1, 2, 3], &[4, 5, 6, 7]];
+        let mut reader = crate::from_slices(chunks.iter().copied()).with_position();
+        assert_eq!(reader.position(), 0);
+        reader.read_byte().unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(reader.position(), 1);
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.position(), 4);
+        reader.fill_buf().unwrap_or_else(|infallible| match infallible {});
+        reader.consume(2);
+        assert_eq!(reader.position(), 6);
+        assert_eq!(reader.stream_position_hint(), Some(6));
+    }
+}
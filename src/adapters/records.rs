@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+use crate::BufWrite;
+
+/// Writes whole records at once - returned from [`BufWrite::records`].
+///
+/// If writing a record fails its buffered part is discarded so that retrying the failed write
+/// doesn't write the beginning of the record twice.
+pub struct RecordWriter<W> {
+    writer: W,
+    delimiter: u8,
+    buffer: Vec<u8>,
+}
+
+impl<W: BufWrite> RecordWriter<W> {
+    pub(crate) fn new(writer: W, delimiter: u8) -> Self {
+        RecordWriter {
+            writer,
+            delimiter,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the bytes of the incomplete record that were not written yet.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// Note that the incomplete record is lost, call [`flush`](BufWrite::flush) first if you
+    /// want to write it.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for RecordWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, mut bytes: &[u8]) -> Result<(), Self::WriteError> {
+        while let Some(pos) = bytes.iter().position(|&byte| byte == self.delimiter) {
+            let (record, rest) = bytes.split_at(pos + 1);
+            if self.buffer.is_empty() {
+                self.writer.write_all(record)?;
+            } else {
+                self.buffer.extend_from_slice(record);
+                let result = self.writer.write_all(&self.buffer);
+                self.buffer.clear();
+                result?;
+            }
+            bytes = rest;
+        }
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Writes the incomplete record and flushes the underlying writer.
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        if !self.buffer.is_empty() {
+            let result = self.writer.write_all(&self.buffer);
+            self.buffer.clear();
+            result?;
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use crate::BufWrite;
+
+    struct Records(Vec<Vec<u8>>);
+
+    impl BufWrite for Records {
+        type WriteError = core::convert::Infallible;
+
+        fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+            self.0.push(bytes.to_vec());
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::WriteError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn records() {
+        let mut writer = Records(Vec::new()).records(b'\n');
+        writer.write_all(b"he").unwrap_or_else(|infallible| match infallible {});
+        writer.write_all(b"llo\nworld\nfoo").unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(writer.buffered(), b"foo");
+        writer.write_all(b"\nbar").unwrap_or_else(|infallible| match infallible {});
+        writer.flush().unwrap_or_else(|infallible| match infallible {});
+        let records = writer.into_inner().0;
+        let expected: [&[u8]; 4] = [b"hello\n", b"world\n", b"foo\n", b"bar"];
+        assert_eq!(records, expected);
+    }
+
+    #[test]
+    fn retry_after_failure() {
+        /// Fails the first write.
+        struct FailOnce {
+            failed: bool,
+            records: Records,
+        }
+
+        impl BufWrite for FailOnce {
+            type WriteError = ();
+
+            fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+                if !self.failed {
+                    self.failed = true;
+                    return Err(());
+                }
+                self.records.write_all(bytes).unwrap_or_else(|infallible| match infallible {});
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+        }
+
+        let mut writer = FailOnce { failed: false, records: Records(Vec::new()) }.records(b'\n');
+        writer.write_all(b"he").unwrap();
+        assert!(writer.write_all(b"llo\n").is_err());
+        assert!(writer.buffered().is_empty());
+        writer.write_all(b"hello\n").unwrap();
+        let records = writer.into_inner().records.0;
+        let expected: [&[u8]; 1] = [b"hello\n"];
+        assert_eq!(records, expected);
+    }
+}
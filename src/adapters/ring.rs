@@ -0,0 +1,170 @@
+use crate::{BufRead, BufWrite};
+
+/// Writes into a fixed buffer, overwriting the oldest data when it's full.
+///
+/// This is useful for keeping the most recent output in a bounded amount of memory, e.g. for
+/// crash logs. Writing never fails. The contents can be read using [`contents`](Self::contents)
+/// or drained using [`reader`](Self::reader).
+///
+/// The buffer can be borrowed (`&mut [u8]`) or owned (e.g. `[u8; N]`).
+pub struct RingWriter<B> {
+    buf: B,
+    start: usize,
+    len: usize,
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> RingWriter<B> {
+    /// Creates an empty ring buffer backed by `buf`.
+    pub fn new(buf: B) -> Self {
+        RingWriter {
+            buf,
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes stored in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bytes stored in the buffer.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of bytes the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Returns the stored bytes as two slices, oldest bytes first.
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let buf = self.buf.as_ref();
+        if self.start + self.len <= buf.len() {
+            (&buf[self.start..(self.start + self.len)], &[])
+        } else {
+            (&buf[self.start..], &buf[..(self.start + self.len - buf.len())])
+        }
+    }
+
+    /// Returns the stored bytes in order, oldest bytes first.
+    pub fn contents(&self) -> impl Iterator<Item=u8> + '_ {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second).copied()
+    }
+
+    /// Returns a reader removing the bytes from the buffer as they are consumed.
+    pub fn reader(&mut self) -> RingReader<'_, B> {
+        RingReader {
+            ring: self,
+        }
+    }
+
+    /// Returns the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> BufWrite for RingWriter<B> {
+    type WriteError = core::convert::Infallible;
+
+    fn write_all(&mut self, mut bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let buf = self.buf.as_mut();
+        let capacity = buf.len();
+        if bytes.len() >= capacity {
+            bytes = &bytes[(bytes.len() - capacity)..];
+            buf.copy_from_slice(bytes);
+            self.start = 0;
+            self.len = capacity;
+            return Ok(());
+        }
+
+        let end = (self.start + self.len) % capacity;
+        let first_len = bytes.len().min(capacity - end);
+        buf[end..(end + first_len)].copy_from_slice(&bytes[..first_len]);
+        buf[..(bytes.len() - first_len)].copy_from_slice(&bytes[first_len..]);
+
+        let overwritten = (self.len + bytes.len()).saturating_sub(capacity);
+        self.start = (self.start + overwritten) % capacity;
+        self.len = self.len + bytes.len() - overwritten;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+/// Drains bytes from [`RingWriter`] - returned from [`RingWriter::reader`].
+pub struct RingReader<'a, B> {
+    ring: &'a mut RingWriter<B>,
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> BufRead for RingReader<'_, B> {
+    type ReadError = core::convert::Infallible;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        Ok(self.ring.as_slices().0)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.ring.as_slices().0.len());
+        self.ring.len -= amount;
+        self.ring.start = if self.ring.len == 0 { 0 } else { (self.ring.start + amount) % self.ring.capacity() };
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.ring.len, Some(self.ring.len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RingWriter;
+    use crate::{BufRead, BufWrite};
+
+    #[test]
+    fn ring_overwrite() {
+        let mut buf = [0; 4];
+        let mut ring = RingWriter::new(&mut buf);
+        ring.write_all(&[1, 2, 3]).unwrap_or_else(|infallible| match infallible {});
+        assert!(ring.contents().eq([1, 2, 3].iter().copied()));
+        ring.write_all(&[4, 5]).unwrap_or_else(|infallible| match infallible {});
+        assert!(ring.contents().eq([2, 3, 4, 5].iter().copied()));
+        ring.write_all(&[6, 7, 8, 9, 10]).unwrap_or_else(|infallible| match infallible {});
+        assert!(ring.contents().eq([7, 8, 9, 10].iter().copied()));
+    }
+
+    #[test]
+    fn ring_owned() {
+        let mut ring = RingWriter::new([0; 3]);
+        ring.write_all(&[1, 2, 3, 4]).unwrap_or_else(|infallible| match infallible {});
+        assert!(ring.contents().eq([2, 3, 4].iter().copied()));
+        assert_eq!(ring.into_inner(), [2, 3, 4]);
+
+        let mut ring = RingWriter::new([0; 0]);
+        ring.write_all(&[1, 2]).unwrap_or_else(|infallible| match infallible {});
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn ring_drain() {
+        let mut buf = [0; 4];
+        let mut ring = RingWriter::new(&mut buf);
+        ring.write_all(&[1, 2, 3, 4, 5, 6]).unwrap_or_else(|infallible| match infallible {});
+        ring.reader().consume(1);
+        ring.write_all(&[7]).unwrap_or_else(|infallible| match infallible {});
+        let mut reader = ring.reader();
+        for expected in 4..=7 {
+            assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(expected));
+        }
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+        assert!(ring.is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{BufRead, BufWrite};
+use crate::error::{RleError, UnexpectedEnd};
+
+const MAX_RUN: usize = u8::MAX as usize;
+
+/// Decodes run-length encoded data - returned from [`BufRead::rle_decode`].
+///
+/// The encoded data is a sequence of (count, byte) pairs, each expanding to `count` copies of
+/// `byte`. Runs of zero length are invalid.
+pub struct RleReader<R> {
+    reader: R,
+    run: [u8; MAX_RUN],
+    remaining: usize,
+}
+
+impl<R: BufRead> RleReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        RleReader {
+            reader,
+            run: [0; MAX_RUN],
+            remaining: 0,
+        }
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that the remaining bytes of the current run are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for RleReader<R> {
+    type ReadError = RleError<R::ReadError>;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.remaining == 0 {
+            let count = match self.reader.read_byte().map_err(RleError::ReadingFailed)? {
+                Some(count) => count,
+                None => return Ok(&[]),
+            };
+            if count == 0 {
+                return Err(RleError::ZeroCount);
+            }
+            let byte = self.reader.read_byte()
+                .map_err(RleError::ReadingFailed)?
+                .ok_or_else(|| UnexpectedEnd::new(2, 1))?;
+
+            self.remaining = count.into();
+            for target in &mut self.run[..self.remaining] {
+                *target = byte;
+            }
+        }
+        Ok(&self.run[..self.remaining])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.remaining);
+        self.remaining -= amount;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.run[..self.remaining]
+    }
+}
+
+/// Encodes written data using run-length encoding - returned from [`BufWrite::rle_encode`].
+///
+/// The output format is the one decoded by [`RleReader`]. The last run is kept in memory until
+/// a different byte is written, the writer is flushed or [`finish`](Self::finish) is called.
+pub struct RleWriter<W> {
+    writer: W,
+    byte: u8,
+    count: u8,
+}
+
+impl<W: BufWrite> RleWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        RleWriter {
+            writer,
+            byte: 0,
+            count: 0,
+        }
+    }
+
+    /// Writes the pending run and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, W::WriteError> {
+        self.write_pending()?;
+        Ok(self.writer)
+    }
+
+    fn write_pending(&mut self) -> Result<(), W::WriteError> {
+        if self.count > 0 {
+            self.writer.write_all(&[self.count, self.byte])?;
+            self.count = 0;
+        }
+        Ok(())
+    }
+}
+
+impl<W: BufWrite> BufWrite for RleWriter<W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut chunk = [0u8; 256];
+        let mut len = 0;
+        // The state is stored only after the runs preceding it were written.
+        let mut run_byte = self.byte;
+        let mut count = self.count;
+        for &byte in bytes {
+            if count > 0 && (byte != run_byte || usize::from(count) == MAX_RUN) {
+                if len + 2 > chunk.len() {
+                    self.writer.write_all(&chunk[..len])?;
+                    self.byte = run_byte;
+                    self.count = count;
+                    len = 0;
+                }
+                chunk[len] = count;
+                chunk[len + 1] = run_byte;
+                len += 2;
+                count = 0;
+            }
+            run_byte = byte;
+            count += 1;
+        }
+        self.writer.write_all(&chunk[..len])?;
+        self.byte = run_byte;
+        self.count = count;
+        Ok(())
+    }
+
+    /// Writes the pending run and flushes the underlying writer.
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.write_pending()?;
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+    use crate::error::RleError;
+
+    #[test]
+    fn rle_round_trip() {
+        let mut input = [7u8; 300];
+        input[299] = 8;
+        let mut buf = [0u8; 6];
+        let mut writer = (&mut buf[..]).rle_encode();
+        writer.write_all(&input[..100]).unwrap();
+        writer.write_all(&input[100..]).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(buf, [255, 7, 44, 7, 1, 8]);
+
+        let mut reader = (&buf[..]).rle_decode();
+        for &expected in &input[..] {
+            assert_eq!(reader.read_byte().unwrap(), Some(expected));
+        }
+        assert_eq!(reader.read_byte().unwrap(), None);
+    }
+
+    #[test]
+    fn rle_retry_after_failure() {
+        /// Fails writes while `fail` is set.
+        struct Flaky<'a> {
+            fail: &'a core::cell::Cell<bool>,
+            buf: &'a mut [u8],
+        }
+
+        impl BufWrite for Flaky<'_> {
+            type WriteError = ();
+
+            fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+                if self.fail.get() {
+                    return Err(());
+                }
+                self.buf.write_all(bytes).map_err(drop)
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+        }
+
+        let fail = core::cell::Cell::new(false);
+        let mut buf = [0u8; 4];
+        let mut writer = Flaky { fail: &fail, buf: &mut buf[..] }.rle_encode();
+        writer.write_all(&[1, 1]).unwrap();
+        fail.set(true);
+        assert!(writer.write_all(&[2]).is_err());
+        fail.set(false);
+        writer.write_all(&[2]).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(buf, [2, 1, 1, 2]);
+    }
+
+    #[test]
+    fn rle_invalid() {
+        assert!(matches!((&[0, 1][..]).rle_decode().fill_buf(), Err(RleError::ZeroCount)));
+        assert!(matches!((&[1][..]).rle_decode().fill_buf(), Err(RleError::UnexpectedEnd(_))));
+    }
+}
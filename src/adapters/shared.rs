@@ -0,0 +1,149 @@
+use core::cell::UnsafeCell;
+use crate::{BufRead, BufWrite};
+use crate::error::WriteAllError;
+
+/// Allows reading and writing through shared references without runtime checks.
+///
+/// This is a cheaper alternative to `RefCell` for single-threaded code that can guarantee
+/// accesses don't overlap. [`BufRead`] and [`BufWrite`] are implemented for `&SharedIo<T>` so
+/// multiple parts of the code can hold a reference to the same reader or writer.
+///
+/// The type is not `Sync` so it can not be shared across threads.
+pub struct SharedIo<T> {
+    inner: UnsafeCell<T>,
+}
+
+impl<T> SharedIo<T> {
+    /// Wraps the reader or writer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that operations through different references don't overlap.
+    /// In particular:
+    ///
+    /// * The slice returned from `fill_buf` called through one reference must not be used after
+    ///   any other method was called through another reference.
+    /// * The wrapped value must not access the `SharedIo` containing it (no reentrancy).
+    pub unsafe fn new(inner: T) -> Self {
+        SharedIo {
+            inner: UnsafeCell::new(inner),
+        }
+    }
+
+    /// Returns a mutable reference to the wrapped value.
+    ///
+    /// This is safe because the exclusive borrow statically prevents any other access.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+
+    fn access_shared(&self) -> &T {
+        // SAFETY: the caller of `new` promised no overlapping access
+        unsafe { &*self.inner.get() }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn access(&self) -> &mut T {
+        // SAFETY: the caller of `new` promised no overlapping access
+        unsafe { &mut *self.inner.get() }
+    }
+}
+
+impl<T: BufRead> BufRead for &SharedIo<T> {
+    type ReadError = T::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        self.access().fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.access().consume(amount)
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.access_shared().buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.access_shared().size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.access_shared().stream_position_hint()
+    }
+}
+
+impl<T: BufWrite> BufWrite for &SharedIo<T> {
+    type WriteError = T::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.access().write_all(bytes)
+    }
+
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        self.access().write_all_counting(bytes)
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        self.access().write_some(bytes)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        self.access().write_byte(byte)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.access().reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.access().flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.access_shared().needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+    use super::SharedIo;
+
+    #[test]
+    fn shared_access() {
+        let mut buf = [0u8; 4];
+        // SAFETY: the references are used one at a time
+        let shared = unsafe { SharedIo::new(&mut buf[..]) };
+        let mut first = &shared;
+        let mut second = &shared;
+        BufWrite::write_all(&mut first, &[1, 2]).unwrap();
+        BufWrite::write_all(&mut second, &[3]).unwrap();
+        assert_eq!(shared.into_inner().len(), 1);
+        assert_eq!(buf, [1, 2, 3, 0]);
+
+        let mut buf = [0u8; 3];
+        // SAFETY: the references are used one at a time
+        let shared = unsafe { SharedIo::new(&mut buf[..]) };
+        let mut first = &shared;
+        let mut second = &shared;
+        assert_eq!(first.write_some(&[1, 2]).unwrap(), 2);
+        assert_eq!(second.write_some(&[3, 4]).unwrap(), 1);
+        assert_eq!(first.write_some(&[5]).unwrap(), 0);
+        assert_eq!(buf, [1, 2, 3]);
+
+        let input = [5u8, 6, 7];
+        // SAFETY: the references are used one at a time
+        let shared = unsafe { SharedIo::new(&input[..]) };
+        let mut first = &shared;
+        let mut second = &shared;
+        assert_eq!(first.read_byte().unwrap(), Some(5));
+        assert_eq!(second.read_byte().unwrap(), Some(6));
+        assert_eq!(first.fill_buf().unwrap(), [7]);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::BufWrite;
+use crate::error::{BufferOverflow, WriteAllError};
+
+/// Writes into a fixed buffer keeping track of its capacity.
+///
+/// Unlike writing into `&mut [u8]` directly this keeps the whole buffer so the written bytes
+/// and the remaining space can be queried even after the writer was passed to generic code.
+/// Writes that don't fit fail with [`BufferOverflow`] without writing anything.
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a writer writing at the beginning of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter {
+            buf,
+            position: 0,
+        }
+    }
+
+    /// Returns the bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.position]
+    }
+
+    /// Returns the number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.position
+    }
+
+    /// Returns the size of the whole buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the written part of the buffer.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.position]
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for SliceWriter<'a> {
+    fn from(buf: &'a mut [u8]) -> Self {
+        SliceWriter::new(buf)
+    }
+}
+
+impl BufWrite for SliceWriter<'_> {
+    type WriteError = BufferOverflow;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        if bytes.len() > self.remaining() {
+            return Err(BufferOverflow::new(bytes.len() - self.remaining()));
+        }
+        self.buf[self.position..(self.position + bytes.len())].copy_from_slice(bytes);
+        self.position += bytes.len();
+        Ok(())
+    }
+
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        crate::sync_impls::write_all_counting_fixed(self, bytes)
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        let len = bytes.len().min(self.remaining());
+        self.write_all(&bytes[..len])?;
+        Ok(len)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        match self.buf.get_mut(self.position) {
+            Some(target) => {
+                *target = byte;
+                self.position += 1;
+                Ok(())
+            },
+            None => Err(BufferOverflow::new(1)),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+    use super::SliceWriter;
+
+    fn encode(mut writer: impl BufWrite) {
+        writer.write_all(&[1, 2, 3]).unwrap_or_else(|_| panic!("overflow"));
+    }
+
+    #[test]
+    fn slice_writer() {
+        let mut buf = [0u8; 4];
+        let mut writer = SliceWriter::new(&mut buf);
+        encode(&mut writer);
+        assert_eq!(writer.written(), [1, 2, 3]);
+        assert_eq!(writer.remaining(), 1);
+        assert_eq!(writer.capacity(), 4);
+        assert!(writer.write_all(&[4, 5]).is_err());
+        assert_eq!(writer.into_written(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn write_all_counting_partial() {
+        let mut buf = [0u8; 4];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.write_all(&[1]).unwrap();
+        let error = writer.write_all_counting(&[2, 3, 4, 5, 6]).unwrap_err();
+        assert_eq!(error.written(), 3);
+        assert_eq!(writer.into_written(), [1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::BufRead;
+
+/// Reads bytes from a sequence of slices - returned from [`crate::from_slices`].
+pub struct SliceIterReader<'a, I> {
+    slices: I,
+    current: &'a [u8],
+}
+
+impl<'a, I: Iterator<Item=&'a [u8]>> SliceIterReader<'a, I> {
+    pub(crate) fn new(slices: I) -> Self {
+        SliceIterReader {
+            slices,
+            current: &[],
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item=&'a [u8]>> BufRead for SliceIterReader<'a, I> {
+    type ReadError = core::convert::Infallible;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        while self.current.is_empty() {
+            match self.slices.next() {
+                Some(slice) => self.current = slice,
+                None => break,
+            }
+        }
+        Ok(self.current)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.current = &self.current[amount..];
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.current
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.slices.size_hint();
+        // Empty slices can not be ruled out so the remaining slices don't affect the lower bound.
+        (self.current.len(), if upper == Some(0) { Some(self.current.len()) } else { None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+
+    #[test]
+    fn read_slices() {
+        let slices: [&[u8]; 4] = [&[1, 2], &[], &[3], &[]];
+        let mut reader = crate::from_slices(slices.iter().copied());
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[1, 2]);
+        reader.consume(1);
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(2));
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[3]);
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), &[]);
+    }
+}
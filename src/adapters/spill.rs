@@ -0,0 +1,90 @@
+use crate::BufWrite;
+use alloc::vec::Vec;
+
+/// Writes into a fixed buffer, spilling the remaining bytes into a `Vec` once it's full.
+///
+/// This optimizes for the common case of small messages fitting into a (stack) buffer while still
+/// supporting rare large ones. The output is the concatenation of the filled part of the buffer
+/// and the bytes appended to the `Vec`.
+pub struct SpillWriter<'a> {
+    buf: &'a mut [u8],
+    filled: usize,
+    overflow: &'a mut Vec<u8>,
+    overflow_start: usize,
+}
+
+impl<'a> SpillWriter<'a> {
+    /// Creates the writer filling `buf` first and then appending to `overflow`.
+    ///
+    /// Existing contents of `overflow` are kept intact.
+    pub fn new(buf: &'a mut [u8], overflow: &'a mut Vec<u8>) -> Self {
+        let overflow_start = overflow.len();
+        SpillWriter {
+            buf,
+            filled: 0,
+            overflow,
+            overflow_start,
+        }
+    }
+
+    /// Returns `true` if any bytes were written into the overflow `Vec`.
+    pub fn is_spilled(&self) -> bool {
+        self.overflow.len() > self.overflow_start
+    }
+
+    /// Returns the part of the buffer that was written to.
+    pub fn filled(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+
+    /// Returns the total number of bytes written.
+    pub fn len(&self) -> usize {
+        self.filled + self.overflow.len() - self.overflow_start
+    }
+
+    /// Returns `true` if nothing was written yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl BufWrite for SpillWriter<'_> {
+    type WriteError = core::convert::Infallible;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let to_copy = (self.buf.len() - self.filled).min(bytes.len());
+        self.buf[self.filled..(self.filled + to_copy)].copy_from_slice(&bytes[..to_copy]);
+        self.filled += to_copy;
+        self.overflow.extend_from_slice(&bytes[to_copy..]);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpillWriter;
+    use crate::BufWrite;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn spill() {
+        let mut buf = [0; 4];
+        let mut overflow = Vec::from([42]);
+        let mut writer = SpillWriter::new(&mut buf, &mut overflow);
+        writer.write_all(&[1, 2, 3]).unwrap_or_else(|infallible| match infallible {});
+        assert!(!writer.is_spilled());
+        writer.write_all(&[4, 5, 6]).unwrap_or_else(|infallible| match infallible {});
+        assert!(writer.is_spilled());
+        assert_eq!(writer.len(), 6);
+        assert_eq!(writer.filled(), &[1, 2, 3, 4]);
+        assert_eq!(overflow, [42, 5, 6]);
+    }
+}
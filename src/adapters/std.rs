@@ -1,5 +1,6 @@
 use crate::{BufRead, BufWrite};
 use std::io;
+use alloc::boxed::Box;
 
 /// Provides [`std::io`] traits for applicable [`BufRead`] and [`BufWrite`] implementors - returned
 /// from [`BufRead::into_std`].
@@ -95,6 +96,48 @@ impl<Io: BufWrite> io::Write for AsStdWriter<Io> where Io::WriteError: Into<io::
     }
 }
 
+/// Writer with erased type and errors converted to [`io::Error`] - returned from
+/// [`BufWrite::erase`].
+pub struct ErasedWrite<'a>(Box<dyn BufWrite<WriteError=io::Error> + 'a>);
+
+impl<'a> ErasedWrite<'a> {
+    pub(crate) fn new<W: BufWrite + 'a>(writer: W) -> Self where W::WriteError: Into<io::Error> {
+        ErasedWrite(Box::new(writer.map_write_err(Into::into)))
+    }
+}
+
+impl BufWrite for ErasedWrite<'_> {
+    type WriteError = io::Error;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.0.write_all(bytes)
+    }
+
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), crate::error::WriteAllError<Self::WriteError>> {
+        self.0.write_all_counting(bytes)
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        self.0.write_some(bytes)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        self.0.write_byte(byte)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.0.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.0.needs_flush()
+    }
+}
+
 /// Provides [`BufRead`] implementation for [`std::io::BufRead`] implementors - returned
 /// from [`crate::from_std_reader`].
 pub struct StdBufRead<Io>(Io);
@@ -103,6 +146,24 @@ impl<Io: io::BufRead> StdBufRead<Io> {
     pub(crate) fn new(io: Io) -> Self {
         StdBufRead(io)
     }
+
+    /// Fills the buffer of a non-blocking reader.
+    ///
+    /// Returns `Ok(None)` if the underlying reader returns [`io::ErrorKind::WouldBlock`] meaning
+    /// there's no data available yet. This is distinct from `Ok(Some(&[]))` which means EOF.
+    /// Interrupted reads are retried.
+    pub fn fill_buf_nonblocking(&mut self) -> Result<Option<&[u8]>, io::Error> {
+        loop {
+            match self.0.fill_buf() {
+                // SAFETY: this works around a borrowchecker bug
+                // See https://github.com/rust-lang/rust/issues/51132
+                Ok(bytes) => break Ok(Some(unsafe { &*(bytes as *const _) })),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => (),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break Ok(None),
+                Err(error) => break Err(error),
+            }
+        }
+    }
 }
 
 impl<Io: io::BufRead> BufRead for StdBufRead<Io> {
@@ -129,3 +190,248 @@ impl<Io: io::BufRead> BufRead for StdBufRead<Io> {
         self.0.read_to_end(buf)
     }
 }
+
+/// Provides [`BufWrite`] implementation for [`std::io::Write`] implementors - returned from
+/// [`crate::from_std_writer`].
+///
+/// Small writes are coalesced in an internal [`io::BufWriter`] so that writing byte-by-byte
+/// doesn't cause a system call per byte. Like `io::BufWriter`, the buffered bytes are written
+/// when the adapter is dropped but any error is ignored, so call [`flush`](BufWrite::flush)
+/// explicitly.
+pub struct StdBufWrite<Io: io::Write>(io::BufWriter<Io>);
+
+impl<Io: io::Write> StdBufWrite<Io> {
+    pub(crate) fn new(io: Io) -> Self {
+        StdBufWrite(io::BufWriter::new(io))
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Io {
+        self.0.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer skips the buffered bytes.
+    pub fn get_mut(&mut self) -> &mut Io {
+        self.0.get_mut()
+    }
+
+    /// Returns the bytes that were not written to the underlying writer yet.
+    pub fn buffer(&self) -> &[u8] {
+        self.0.buffer()
+    }
+
+    /// Writes the buffered bytes and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from writing the buffered bytes, which contains the buffered writer.
+    pub fn into_inner(self) -> Result<Io, io::IntoInnerError<io::BufWriter<Io>>> {
+        self.0.into_inner()
+    }
+
+    /// Writes as many bytes as possible into a non-blocking writer.
+    ///
+    /// Unlike [`BufWrite::write_all`] this treats [`io::ErrorKind::WouldBlock`] as a signal to
+    /// stop rather than an error and returns the number of bytes accepted by the underlying
+    /// writer. The caller is expected to wait for the writer to become ready (e.g. using `mio` or
+    /// `epoll`) and then write the rest. Interrupted writes are retried.
+    ///
+    /// The previously buffered bytes are written first and if the writer would block before all
+    /// of them are written `Ok(0)` is returned. The bytes passed to this method are never
+    /// buffered so the returned number reflects what the underlying writer really accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error other than `WouldBlock` or `Interrupted`. Returns
+    /// [`io::ErrorKind::WriteZero`] if the writer accepts none of the bytes without blocking. If
+    /// it stops accepting them after some were written the number of written bytes is returned
+    /// instead.
+    pub fn write_all_nonblocking(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        match io::Write::flush(&mut self.0) {
+            Ok(()) => (),
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(0),
+            Err(error) => return Err(error),
+        }
+        let writer = self.0.get_mut();
+        let mut written = 0;
+        while written < bytes.len() {
+            match writer.write(&bytes[written..]) {
+                Ok(0) if written == 0 => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(0) => break,
+                Ok(amount) => written += amount,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => (),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl<Io: io::Write> BufWrite for StdBufWrite<Io> {
+    type WriteError = io::Error;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        io::Write::write_all(&mut self.0, bytes)
+    }
+
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), crate::error::WriteAllError<Self::WriteError>> {
+        crate::sync_impls::write_all_counting(&mut self.0, bytes)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        io::Write::flush(&mut self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::vec::Vec;
+    use crate::BufWrite;
+
+    #[test]
+    fn erased_write() {
+        let mut first = io::BufWriter::new(Vec::new());
+        let mut second = io::BufWriter::new(Vec::new());
+        {
+            let mut writers = [first.by_ref().erase(), second.by_ref().erase()];
+            for writer in &mut writers {
+                writer.write_all(b"hello").unwrap();
+                writer.flush().unwrap();
+            }
+            let dynamic: &mut dyn BufWrite<WriteError=io::Error> = &mut writers[0];
+            dynamic.write_all(b"!").unwrap();
+            dynamic.flush().unwrap();
+        }
+        assert_eq!(first.get_ref(), b"hello!");
+        assert_eq!(second.get_ref(), b"hello");
+    }
+
+    #[test]
+    fn fill_buf_nonblocking() {
+        struct Pending(Option<&'static [u8]>);
+
+        impl io::Read for Pending {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                match self.0.take() {
+                    Some(bytes) => {
+                        buf[..bytes.len()].copy_from_slice(bytes);
+                        Ok(bytes.len())
+                    },
+                    None => Err(io::ErrorKind::WouldBlock.into()),
+                }
+            }
+        }
+
+        let mut reader = crate::from_std_reader(io::BufReader::new(Pending(Some(&[1, 2]))));
+        assert_eq!(reader.fill_buf_nonblocking().unwrap(), Some(&[1, 2][..]));
+        crate::BufRead::consume(&mut reader, 2);
+        assert_eq!(reader.fill_buf_nonblocking().unwrap(), None);
+
+        let mut reader = crate::from_std_reader(io::empty());
+        assert_eq!(reader.fill_buf_nonblocking().unwrap(), Some(&[][..]));
+    }
+
+    /// Unbuffered writer counting the calls to `write`.
+    #[derive(Debug)]
+    struct CountCalls {
+        data: Vec<u8>,
+        calls: usize,
+    }
+
+    impl io::Write for CountCalls {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn std_writer() {
+        let mut writer = crate::from_std_writer(Vec::new());
+        writer.write_all(b"hello").unwrap();
+        writer.write_byte(b'!').unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.into_inner().unwrap(), b"hello!");
+    }
+
+    #[test]
+    fn std_writer_coalesces() {
+        let mut writer = crate::from_std_writer(CountCalls { data: Vec::new(), calls: 0 });
+        for byte in 0..100 {
+            writer.write_byte(byte).unwrap();
+        }
+        assert_eq!(writer.get_ref().calls, 0);
+        assert_eq!(writer.buffer().len(), 100);
+        writer.flush().unwrap();
+        let inner = writer.into_inner().unwrap();
+        assert_eq!(inner.calls, 1);
+        assert_eq!(inner.data, (0..100).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn write_all_nonblocking_stops_at_would_block() {
+        /// Writer accepting at most two bytes per call and `.0` bytes in total before blocking.
+        struct Limited(usize);
+
+        impl io::Write for Limited {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(io::ErrorKind::WouldBlock.into());
+                }
+                let amount = buf.len().min(2).min(self.0);
+                self.0 -= amount;
+                Ok(amount)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = crate::from_std_writer(Limited(5));
+        assert_eq!(writer.write_all_nonblocking(&[0; 8]).unwrap(), 5);
+        assert_eq!(writer.write_all_nonblocking(&[0; 8]).unwrap(), 0);
+        writer.get_mut().0 = 8;
+        assert_eq!(writer.write_all_nonblocking(&[0; 3]).unwrap(), 3);
+
+        writer.write_all(&[0; 4]).unwrap();
+        writer.get_mut().0 = 2;
+        assert_eq!(writer.write_all_nonblocking(&[0; 3]).unwrap(), 0);
+        assert_eq!(writer.buffer().len(), 2);
+        writer.get_mut().0 = 4;
+        assert_eq!(writer.write_all_nonblocking(&[0; 3]).unwrap(), 2);
+        assert!(writer.buffer().is_empty());
+    }
+
+    #[test]
+    fn write_all_nonblocking_reports_progress_before_write_zero() {
+        /// Writer accepting one byte per call and `.0` bytes in total before returning zero.
+        struct Full(usize);
+
+        impl io::Write for Full {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let amount = buf.len().min(1).min(self.0);
+                self.0 -= amount;
+                Ok(amount)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = crate::from_std_writer(Full(2));
+        assert_eq!(writer.write_all_nonblocking(&[0; 4]).unwrap(), 2);
+        let error = writer.write_all_nonblocking(&[0; 2]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::string::String;
+use core::str::Utf8Error;
+use crate::BufWrite;
+use super::utf8::PendingChar;
+
+/// Appends UTF-8 bytes to a borrowed [`String`] validating them.
+///
+/// Multi-byte characters may be split across multiple writes - the incomplete character is kept
+/// in a small internal buffer until the rest arrives. If invalid bytes are written the valid
+/// prefix is appended and the string is left untouched past it. The invalid bytes are discarded
+/// so the writer can be used for further writes.
+pub struct StrRefWriter<'a> {
+    string: &'a mut String,
+    pending: PendingChar,
+}
+
+impl<'a> StrRefWriter<'a> {
+    /// Creates the writer appending to `string`.
+    pub fn new(string: &'a mut String) -> Self {
+        StrRefWriter {
+            string,
+            pending: PendingChar::new(),
+        }
+    }
+
+    /// Returns the string written so far excluding any incomplete character.
+    pub fn as_str(&self) -> &str {
+        self.string
+    }
+
+    /// Returns the string checking that the last character is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes written so far end with an incomplete character.
+    pub fn finish(self) -> Result<&'a mut String, Utf8Error> {
+        self.pending.check_complete()?;
+        Ok(self.string)
+    }
+
+    /// Returns the string discarding any incomplete character.
+    pub fn into_inner(self) -> &'a mut String {
+        self.string
+    }
+
+    /// Appends the valid prefix of `bytes` keeping the incomplete character at the end.
+    fn push_valid(&mut self, bytes: &[u8]) -> Result<(), Utf8Error> {
+        match core::str::from_utf8(bytes) {
+            Ok(s) => {
+                self.string.push_str(s);
+                Ok(())
+            },
+            Err(error) => {
+                let (valid, rest) = bytes.split_at(error.valid_up_to());
+                // SAFETY: from_utf8 checked the bytes up to valid_up_to
+                self.string.push_str(unsafe { core::str::from_utf8_unchecked(valid) });
+                if error.error_len().is_some() {
+                    return Err(error);
+                }
+                self.pending.set(rest);
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a mut String> for StrRefWriter<'a> {
+    fn from(string: &'a mut String) -> Self {
+        StrRefWriter::new(string)
+    }
+}
+
+impl BufWrite for StrRefWriter<'_> {
+    type WriteError = Utf8Error;
+
+    /// Validates the bytes and appends them to the string.
+    ///
+    /// If the bytes are invalid the valid prefix is written and an error is returned. If a
+    /// character split across writes turns out to be invalid only that character is discarded,
+    /// the following bytes are written as usual and the error is returned.
+    fn write_all(&mut self, mut bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut result = Ok(());
+        if !self.pending.is_empty() {
+            let (consumed, complete) = self.pending.complete(bytes);
+            match complete {
+                Ok(Some(s)) => {
+                    self.string.push_str(s);
+                    self.pending.clear();
+                },
+                Ok(None) => (),
+                Err(error) => result = Err(error),
+            }
+            bytes = &bytes[consumed..];
+        }
+        let written = self.push_valid(bytes);
+        result.and(written)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.string.reserve(additional);
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use crate::BufWrite;
+    use super::StrRefWriter;
+
+    #[test]
+    fn split_characters() {
+        let mut string = String::from(">");
+        let mut writer = StrRefWriter::new(&mut string);
+        for byte in "a€ř".as_bytes() {
+            writer.write_all(core::slice::from_ref(byte)).unwrap();
+        }
+        writer.finish().unwrap();
+        assert_eq!(string, ">a€ř");
+    }
+
+    #[test]
+    fn invalid_keeps_valid_prefix() {
+        let mut string = String::new();
+        let mut writer = StrRefWriter::new(&mut string);
+        writer.write_all(b"ab").unwrap();
+        assert!(writer.write_all(b"c\xffd").is_err());
+        assert_eq!(writer.as_str(), "abc");
+        writer.write_all(&"€".as_bytes()[..2]).unwrap();
+        assert!(writer.finish().is_err());
+        assert_eq!(string, "abc");
+    }
+
+    #[test]
+    fn write_after_invalid_continuation() {
+        let mut string = String::new();
+        let mut writer = StrRefWriter::new(&mut string);
+        writer.write_all(&"€".as_bytes()[..1]).unwrap();
+        assert!(writer.write_all(b"ab").is_err());
+        assert_eq!(writer.as_str(), "ab");
+        writer.write_all(&"€".as_bytes()[..2]).unwrap();
+        assert!(writer.write_all(&"ř".as_bytes()[..1]).is_err());
+        writer.write_all(&"ř".as_bytes()[1..]).unwrap();
+        writer.write_all(&[0xF0]).unwrap();
+        assert!(writer.write_all(&[0x9F, b'c']).is_err());
+        writer.write_all("ř".as_bytes()).unwrap();
+        writer.write_all(&"€".as_bytes()[..2]).unwrap();
+        writer.write_all(&"€".as_bytes()[2..]).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(string, "abřcř€");
+    }
+}
@@ -0,0 +1,69 @@
+use crate::BufWrite;
+use super::RingWriter;
+
+/// Remembers the last `N` written bytes - returned from [`BufWrite::keep_tail`].
+pub struct TailWriter<W, const N: usize> {
+    writer: W,
+    tail: RingWriter<[u8; N]>,
+}
+
+impl<W: BufWrite, const N: usize> TailWriter<W, N> {
+    pub(crate) fn new(writer: W) -> Self {
+        TailWriter {
+            writer,
+            tail: RingWriter::new([0; N]),
+        }
+    }
+
+    /// Returns the last (at most) `N` bytes successfully written, oldest first.
+    pub fn tail(&self) -> impl Iterator<Item=u8> + '_ {
+        self.tail.contents()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite, const N: usize> BufWrite for TailWriter<W, N> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.writer.write_all(bytes)?;
+        self.tail.write_all(bytes).unwrap_or_else(|infallible| match infallible {});
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn tail() {
+        let mut buf = [0u8; 8];
+        let mut writer = (&mut buf[..]).keep_tail::<3>();
+        assert_eq!(writer.tail().count(), 0);
+        writer.write_all(&[1, 2]).unwrap();
+        assert!(writer.tail().eq([1, 2]));
+        writer.write_all(&[3, 4]).unwrap();
+        assert!(writer.tail().eq([2, 3, 4]));
+        writer.write_all(&[5, 6, 7, 8]).unwrap();
+        assert!(writer.tail().eq([6, 7, 8]));
+        assert!(writer.write_all(&[9]).is_err());
+        assert!(writer.tail().eq([6, 7, 8]));
+    }
+}
@@ -1,5 +1,6 @@
 use core::convert::TryFrom;
-use crate::BufRead;
+use crate::{BufRead, BufWrite};
+use crate::error::{LimitExceeded, WriteTakeError};
 
 /// Provides a limited number of bytes from underlying reader - returned from [`BufRead::take`].
 pub struct Take<R> {
@@ -18,6 +19,11 @@ impl<R: BufRead> Take<R> {
             last_len: 0,
         }
     }
+
+    /// Returns the number of bytes that can still be read before the limit is reached.
+    pub fn remaining(&self) -> u64 {
+        self.limit
+    }
 }
 
 impl<R: BufRead> BufRead for Take<R> {
@@ -44,6 +50,92 @@ impl<R: BufRead> BufRead for Take<R> {
 
         self.reader.consume(amount);
     }
+
+    fn buffer(&self) -> &[u8] {
+        let buf = self.reader.buffer();
+        &buf[..min(self.limit, buf.len())]
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.reader.size_hint();
+        let lower = min(self.limit, lower);
+        let upper = match (usize::try_from(self.limit), upper) {
+            (Ok(limit), Some(upper)) => Some(limit.min(upper)),
+            (Ok(limit), None) => Some(limit),
+            (Err(_), upper) => upper,
+        };
+        (lower, upper)
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.stream_position_hint()
+    }
+}
+
+/// Accepts a limited number of bytes - returned from [`BufWrite::take_write`].
+pub struct WriteTake<W> {
+    writer: W,
+    limit: u64,
+    remaining: u64,
+}
+
+impl<W: BufWrite> WriteTake<W> {
+    pub(crate) fn new(writer: W, limit: u64) -> Self {
+        WriteTake {
+            writer,
+            limit,
+            remaining: limit,
+        }
+    }
+
+    /// Returns the number of bytes that can still be written before the limit is reached.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for WriteTake<W> {
+    type WriteError = WriteTakeError<W::WriteError>;
+
+    /// Writes the bytes if they fit within the limit.
+    ///
+    /// Writing exactly up to the limit succeeds. If the bytes would exceed the limit nothing is
+    /// written and an error is returned.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        if bytes.len() as u64 > self.remaining {
+            return Err(LimitExceeded::new(self.limit).into());
+        }
+        self.writer.write_all(bytes).map_err(WriteTakeError::WritingFailed)?;
+        self.remaining -= bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Writes as many bytes as the underlying writer accepts without exceeding the limit.
+    ///
+    /// Returns `Ok(0)` once the limit is reached.
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        let bytes = &bytes[..min(self.remaining, bytes.len())];
+        let written = self.writer.write_some(bytes).map_err(WriteTakeError::WritingFailed)?;
+        self.remaining -= written as u64;
+        Ok(written)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(min(self.remaining, additional))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush().map_err(WriteTakeError::WritingFailed)
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
 }
 
 fn min(a: u64, b: usize) -> usize {
@@ -55,7 +147,8 @@ fn min(a: u64, b: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use crate::BufRead;
+    use crate::{BufRead, BufWrite};
+    use crate::error::WriteTakeError;
 
     #[test]
     fn take_zero() {
@@ -75,4 +168,54 @@ mod tests {
         let mut buf = [0; 1];
         assert!(reader.read_exact(&mut buf).is_err());
     }
+
+    #[test]
+    fn take_hints() {
+        let reader = (&[1, 2, 3]).take(2);
+        assert_eq!(reader.size_hint(), (2, Some(2)));
+        assert_eq!(reader.buffer(), &[1, 2]);
+        let reader = crate::empty().take(2);
+        assert_eq!(reader.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn write_take_boundary() {
+        let mut buf = [0u8; 8];
+        let mut writer = (&mut buf[..]).take_write(3);
+        writer.write_all(&[1, 2]).unwrap();
+        assert!(matches!(writer.write_all(&[3, 4]), Err(WriteTakeError::LimitExceeded(_))));
+        assert_eq!(writer.remaining(), 1);
+        writer.write_all(&[3]).unwrap();
+        writer.write_all(&[]).unwrap();
+        assert!(matches!(writer.write_all(&[4]), Err(WriteTakeError::LimitExceeded(_))));
+        assert_eq!(writer.into_inner().len(), 5);
+        assert_eq!(buf[..4], [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn write_take_write_some() {
+        let mut buf = [0u8; 8];
+        let mut writer = (&mut buf[..]).take_write(3);
+        assert_eq!(writer.write_some(&[1, 2]).unwrap(), 2);
+        assert_eq!(writer.write_some(&[3, 4]).unwrap(), 1);
+        assert_eq!(writer.write_some(&[5]).unwrap(), 0);
+        assert_eq!(writer.remaining(), 0);
+        assert_eq!(writer.into_inner().len(), 5);
+        assert_eq!(buf[..4], [1, 2, 3, 0]);
+
+        let mut buf = [0u8; 2];
+        let mut writer = (&mut buf[..]).take_write(3);
+        assert_eq!(writer.write_some(&[1, 2, 3]).unwrap(), 2);
+        assert_eq!(writer.remaining(), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn take_on_read_write_types() {
+        let mut deque = alloc::collections::VecDeque::new();
+        (&mut deque).take_write(2).write_all(&[1, 2]).unwrap();
+        let mut reader = (&mut deque).take(1);
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(1));
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+    }
 }
@@ -0,0 +1,105 @@
+use crate::{BufRead, BufWrite};
+use crate::error::TeeError;
+use super::consumed::ConsumedBytes;
+
+/// Writes all consumed bytes to a writer - returned from [`BufRead::tee`].
+///
+/// Since `consume` can not fail, write errors are deferred: the first error is stored and
+/// returned from the next call to [`fill_buf`](BufRead::fill_buf). The bytes are consumed from
+/// the reader regardless of the error. Bytes consumed while an error is pending are not written.
+/// After the error is returned the adapter continues writing.
+///
+/// Similarly, if the consumed bytes can not be retrieved from the underlying reader without IO
+/// and getting them fails, they are not written and the read error is returned from the next
+/// call to `fill_buf`.
+pub struct Tee<R: BufRead, W: BufWrite> {
+    reader: ConsumedBytes<R>,
+    writer: W,
+    error: Option<W::WriteError>,
+}
+
+impl<R: BufRead, W: BufWrite> Tee<R, W> {
+    pub(crate) fn new(reader: R, writer: W) -> Self {
+        Tee {
+            reader: ConsumedBytes::new(reader),
+            writer,
+            error: None,
+        }
+    }
+
+    /// Returns the pending write error, if any, clearing it.
+    pub fn take_error(&mut self) -> Option<W::WriteError> {
+        self.error.take()
+    }
+
+    /// Returns the underlying reader and writer.
+    ///
+    /// The pending write error, if any, is lost.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader.into_inner(), self.writer)
+    }
+}
+
+impl<R: BufRead, W: BufWrite> BufRead for Tee<R, W> {
+    type ReadError = TeeError<R::ReadError, W::WriteError>;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if let Some(error) = self.error.take() {
+            return Err(TeeError::WritingFailed(error));
+        }
+        self.reader.fill_buf().map_err(TeeError::ReadingFailed)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if self.error.is_some() {
+            return self.reader.consume(amount);
+        }
+        let writer = &mut self.writer;
+        let error = &mut self.error;
+        self.reader.consume_with(amount, |bytes| *error = writer.write_all(bytes).err())
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.get_ref().buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.get_ref().size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.get_ref().stream_position_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+    use crate::error::TeeError;
+
+    #[test]
+    fn tee() {
+        let mut copy = [0u8; 4];
+        let mut reader = (&[1, 2, 3][..]).tee(&mut copy[..]);
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert!(reader.fill_buf().unwrap().is_empty());
+        let (_, remaining) = reader.into_inner();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(copy, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn deferred_error() {
+        let mut copy = [0u8; 2];
+        let mut reader = (&[1, 2, 3, 4][..]).tee(&mut copy[..]);
+        reader.consume(3);
+        assert!(matches!(reader.fill_buf(), Err(TeeError::WritingFailed(_))));
+        assert_eq!(reader.fill_buf().unwrap(), [4]);
+        reader.consume(1);
+        assert!(reader.take_error().is_none());
+        let (reader, _) = reader.into_inner();
+        assert!(reader.is_empty());
+        assert_eq!(copy, [4, 0]);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::BufRead;
+
+/// Reads from a slice keeping track of the number of consumed bytes.
+///
+/// Unlike reading from `&[u8]` directly this remembers the whole slice so the original length
+/// and the number of consumed bytes can be queried after reading.
+#[derive(Debug, Clone)]
+pub struct TrackedSliceReader<'a> {
+    slice: &'a [u8],
+    consumed: usize,
+}
+
+impl<'a> TrackedSliceReader<'a> {
+    /// Creates a reader reading from the beginning of `slice`.
+    pub fn new(slice: &'a [u8]) -> Self {
+        TrackedSliceReader {
+            slice,
+            consumed: 0,
+        }
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Returns the bytes that were not consumed yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.slice[self.consumed..]
+    }
+
+    /// Returns the length of the whole slice.
+    pub fn total_len(&self) -> usize {
+        self.slice.len()
+    }
+}
+
+impl<'a> From<&'a [u8]> for TrackedSliceReader<'a> {
+    fn from(slice: &'a [u8]) -> Self {
+        TrackedSliceReader::new(slice)
+    }
+}
+
+impl BufRead for TrackedSliceReader<'_> {
+    type ReadError = core::convert::Infallible;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.remaining().len());
+        self.consumed += amount;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.remaining()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining().len();
+        (len, Some(len))
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        Some(self.consumed as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+    use super::TrackedSliceReader;
+
+    fn skip_header(mut reader: impl BufRead<ReadError=core::convert::Infallible>) {
+        let mut header = [0; 2];
+        reader.read_exact(&mut header).unwrap();
+    }
+
+    #[test]
+    fn tracked() {
+        let mut reader = TrackedSliceReader::new(&[1, 2, 3, 4]);
+        skip_header(&mut reader);
+        assert_eq!(reader.consumed(), 2);
+        assert_eq!(reader.remaining(), [3, 4]);
+        assert_eq!(reader.total_len(), 4);
+        assert_eq!(reader.stream_position_hint(), Some(2));
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::vec::Vec;
+use crate::BufWrite;
+
+/// Delays all writes until they are committed - returned from [`BufWrite::transaction`].
+///
+/// All written bytes are kept in memory until [`commit`](Self::commit) is called so the memory
+/// cost is the size of the whole transaction.
+pub struct TransactionalWriter<W> {
+    writer: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: BufWrite> TransactionalWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        TransactionalWriter {
+            writer,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the bytes written in this transaction so far.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Writes all buffered bytes into the underlying writer and returns it.
+    ///
+    /// The underlying writer is not flushed.
+    pub fn commit(mut self) -> Result<W, W::WriteError> {
+        self.writer.write_all(&self.buffer)?;
+        Ok(self.writer)
+    }
+
+    /// Discards all buffered bytes and returns the underlying writer.
+    ///
+    /// This is the same as dropping the adapter except the writer is returned.
+    pub fn abort(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for TransactionalWriter<W> {
+    type WriteError = core::convert::Infallible;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    /// Does nothing since the bytes are only written on commit.
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn commit_and_abort() {
+        let mut buf = [0u8; 4];
+        let mut transaction = (&mut buf[..]).transaction();
+        transaction.write_all(&[1, 2]).unwrap_or_else(|infallible| match infallible {});
+        transaction.flush().unwrap_or_else(|infallible| match infallible {});
+        let mut writer = transaction.commit().unwrap();
+        let mut transaction = writer.by_ref().transaction();
+        transaction.write_all(&[3]).unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(transaction.buffered(), [3]);
+        transaction.abort();
+        assert_eq!(writer.len(), 2);
+        assert_eq!(buf, [1, 2, 0, 0]);
+    }
+}
@@ -0,0 +1,184 @@
+use core::str::Utf8Error;
+use crate::BufRead;
+use crate::error::Utf8ReadError;
+use super::consumed::ConsumedBytes;
+
+/// Validates that the consumed bytes are UTF-8 - returned from [`BufRead::validate_utf8`].
+///
+/// If the consumed bytes can not be retrieved from the underlying reader without IO and getting
+/// them fails, the read error is returned from the next call to [`fill_buf`](BufRead::fill_buf).
+/// The validity of the remaining bytes is unknown in such case so the reader should not be used
+/// anymore.
+pub struct Utf8Validator<R: BufRead> {
+    reader: ConsumedBytes<R>,
+    pending: PendingChar,
+    error: Option<Utf8Error>,
+}
+
+impl<R: BufRead> Utf8Validator<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Utf8Validator {
+            reader: ConsumedBytes::new(reader),
+            pending: PendingChar::new(),
+            error: None,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+/// Incomplete UTF-8 character kept between chunks of bytes.
+pub(crate) struct PendingChar {
+    bytes: [u8; 4],
+    len: usize,
+}
+
+impl PendingChar {
+    pub(crate) fn new() -> Self {
+        PendingChar {
+            bytes: [0; 4],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Stores the incomplete character at the end of a chunk.
+    pub(crate) fn set(&mut self, bytes: &[u8]) {
+        self.bytes[..bytes.len()].copy_from_slice(bytes);
+        self.len = bytes.len();
+    }
+
+    /// Returns an error if there is an incomplete character.
+    pub(crate) fn check_complete(&self) -> Result<(), Utf8Error> {
+        core::str::from_utf8(&self.bytes[..self.len]).map(drop)
+    }
+
+    /// Continues the character with the beginning of `bytes`.
+    ///
+    /// Returns the number of bytes taken from `bytes` and the character if it's complete now. The
+    /// complete character is not cleared so that the caller can keep it if storing it fails.
+    ///
+    /// If the bytes are not a valid continuation of the character an error is returned. The
+    /// invalid character is discarded in such case and only the bytes belonging to it are taken
+    /// so that the following bytes can be processed as usual.
+    pub(crate) fn complete(&mut self, bytes: &[u8]) -> (usize, Result<Option<&str>, Utf8Error>) {
+        let width = match self.bytes[0] {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            _ => 4,
+        };
+        let prev_len = self.len;
+        let to_copy = (width - self.len).min(bytes.len());
+        self.bytes[self.len..(self.len + to_copy)].copy_from_slice(&bytes[..to_copy]);
+        self.len += to_copy;
+        match core::str::from_utf8(&self.bytes[..self.len]) {
+            Ok(s) => (to_copy, Ok(Some(s))),
+            Err(error) => match error.error_len() {
+                Some(error_len) => {
+                    self.len = 0;
+                    let invalid_len = error.valid_up_to() + error_len;
+                    (invalid_len.saturating_sub(prev_len), Err(error))
+                },
+                None => (to_copy, Ok(None)),
+            },
+        }
+    }
+}
+
+/// Validates `bytes` continuing the incomplete character in `pending`.
+fn validate(pending: &mut PendingChar, mut bytes: &[u8]) -> Result<(), Utf8Error> {
+    if !pending.is_empty() {
+        let (consumed, complete) = pending.complete(bytes);
+        if complete?.is_none() {
+            return Ok(());
+        }
+        pending.clear();
+        bytes = &bytes[consumed..];
+    }
+    if let Err(error) = core::str::from_utf8(bytes) {
+        if error.error_len().is_some() {
+            return Err(error);
+        }
+        pending.set(&bytes[error.valid_up_to()..]);
+    }
+    Ok(())
+}
+
+impl<R: BufRead> BufRead for Utf8Validator<R> {
+    type ReadError = Utf8ReadError<R::ReadError>;
+
+    /// Returns the buffer of the underlying reader.
+    ///
+    /// Returns an error if the previously consumed bytes are invalid or if the stream ends with
+    /// an incomplete character.
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+        let buf = self.reader.fill_buf().map_err(Utf8ReadError::ReadingFailed)?;
+        if buf.is_empty() {
+            self.pending.check_complete()?;
+        }
+        Ok(buf)
+    }
+
+    /// Consumes the bytes validating them.
+    ///
+    /// If the bytes are invalid the error is returned from the next call to `fill_buf`.
+    fn consume(&mut self, amount: usize) {
+        if self.error.is_some() {
+            return self.reader.consume(amount);
+        }
+        let pending = &mut self.pending;
+        let error = &mut self.error;
+        self.reader.consume_with(amount, |bytes| *error = validate(pending, bytes).err())
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.get_ref().buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.get_ref().size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.get_ref().stream_position_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+    use crate::error::Utf8ReadError;
+
+    #[test]
+    fn split_characters() {
+        let text = "a€ř";
+        let chunks = [&text.as_bytes()[..2], &text.as_bytes()[2..5], &text.as_bytes()[5..]];
+        let mut reader = crate::from_slices(chunks.iter().copied()).validate_utf8();
+        while reader.read_byte().unwrap().is_some() {}
+    }
+
+    #[test]
+    fn invalid() {
+        let mut reader = (&[b'a', 0xFF, b'b'][..]).validate_utf8();
+        assert_eq!(reader.read_byte().unwrap(), Some(b'a'));
+        assert_eq!(reader.read_byte().unwrap(), Some(0xFF));
+        assert!(matches!(reader.fill_buf(), Err(Utf8ReadError::InvalidUtf8(_))));
+
+        let mut reader = (&"€".as_bytes()[..2]).validate_utf8();
+        reader.consume(2);
+        assert!(matches!(reader.fill_buf(), Err(Utf8ReadError::InvalidUtf8(_))));
+    }
+}
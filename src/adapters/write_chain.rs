@@ -0,0 +1,107 @@
+use core::convert::TryFrom;
+use crate::BufWrite;
+
+/// Writes the first bytes to one writer and the rest to another - returned from
+/// [`BufWrite::chain_at`].
+///
+/// Writes straddling the boundary are split: the bytes before the boundary are written to the
+/// first writer, then the rest to the second one. If writing into the second writer fails, the
+/// bytes written into the first one stay written.
+pub struct WriteChain<A, B> {
+    first: A,
+    second: B,
+    remaining: u64,
+}
+
+impl<A: BufWrite, B: BufWrite<WriteError=A::WriteError>> WriteChain<A, B> {
+    pub(crate) fn new(first: A, boundary: u64, second: B) -> Self {
+        WriteChain {
+            first,
+            second,
+            remaining: boundary,
+        }
+    }
+
+    /// Returns the number of bytes that will still be written to the first writer.
+    pub fn first_remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the underlying writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// Returns the length of the prefix of `len` bytes belonging to the first writer.
+    fn first_len(&self, len: usize) -> usize {
+        usize::try_from(self.remaining).map_or(len, |remaining| remaining.min(len))
+    }
+}
+
+impl<A: BufWrite, B: BufWrite<WriteError=A::WriteError>> BufWrite for WriteChain<A, B> {
+    type WriteError = A::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let (first, second) = bytes.split_at(self.first_len(bytes.len()));
+        if !first.is_empty() {
+            self.first.write_all(first)?;
+            self.remaining -= first.len() as u64;
+        }
+        if !second.is_empty() {
+            self.second.write_all(second)?;
+        }
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        let first = self.first_len(additional);
+        if first > 0 {
+            self.first.reserve(first);
+        }
+        if additional > first {
+            self.second.reserve(additional - first);
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.first.flush()?;
+        self.second.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.first.needs_flush() || self.second.needs_flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufWrite;
+
+    #[test]
+    fn straddle() {
+        let mut header = [0u8; 3];
+        let mut body = [0u8; 4];
+        let mut writer = (&mut header[..]).chain_at(3, &mut body[..]);
+        writer.write_all(&[1, 2]).unwrap();
+        writer.write_all(&[3, 4, 5]).unwrap();
+        assert_eq!(writer.first_remaining(), 0);
+        writer.write_all(&[6]).unwrap();
+        let (header_rest, body_rest) = writer.into_inner();
+        assert!(header_rest.is_empty());
+        assert_eq!(body_rest.len(), 1);
+        assert_eq!(header, [1, 2, 3]);
+        assert_eq!(body, [4, 5, 6, 0]);
+    }
+
+    #[test]
+    fn exact_boundary() {
+        let mut header = [0u8; 2];
+        let mut body = [0u8; 2];
+        let mut writer = (&mut header[..]).chain_at(2, &mut body[..]);
+        writer.write_all(&[1, 2]).unwrap();
+        writer.write_all(&[3, 4]).unwrap();
+        assert!(writer.write_all(&[5]).is_err());
+        assert_eq!(header, [1, 2]);
+        assert_eq!(body, [3, 4]);
+    }
+}
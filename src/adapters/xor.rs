@@ -0,0 +1,155 @@
+use crate::{BufRead, BufWrite};
+
+const BUF_SIZE: usize = 256;
+
+/// XORs `bytes` with the `key` repeated from `key_pos`, returns the new position in the key.
+fn xor(bytes: &mut [u8], key: &[u8], mut key_pos: usize) -> usize {
+    if key.is_empty() {
+        return 0;
+    }
+    for byte in bytes {
+        *byte ^= key[key_pos];
+        key_pos = (key_pos + 1) % key.len();
+    }
+    key_pos
+}
+
+/// XORs the written bytes with a repeating key - returned from [`BufWrite::xor_write`].
+///
+/// This is **not** encryption, just a simple obfuscation.
+pub struct XorWriter<'a, W> {
+    writer: W,
+    key: &'a [u8],
+    key_pos: usize,
+}
+
+impl<'a, W: BufWrite> XorWriter<'a, W> {
+    pub(crate) fn new(writer: W, key: &'a [u8]) -> Self {
+        XorWriter {
+            writer,
+            key,
+            key_pos: 0,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: BufWrite> BufWrite for XorWriter<'_, W> {
+    type WriteError = W::WriteError;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        let mut chunk = [0u8; BUF_SIZE];
+        for bytes in bytes.chunks(BUF_SIZE) {
+            let chunk = &mut chunk[..bytes.len()];
+            chunk.copy_from_slice(bytes);
+            let key_pos = xor(chunk, self.key, self.key_pos);
+            self.writer.write_all(chunk)?;
+            self.key_pos = key_pos;
+        }
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.writer.reserve(additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        self.writer.flush()
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.writer.needs_flush()
+    }
+}
+
+/// XORs the read bytes with a repeating key - returned from [`BufRead::xor`].
+///
+/// This is **not** decryption, just a simple deobfuscation.
+pub struct XorReader<'a, R> {
+    reader: R,
+    key: &'a [u8],
+    key_pos: usize,
+    buf: [u8; BUF_SIZE],
+    pos: usize,
+    len: usize,
+}
+
+impl<'a, R: BufRead> XorReader<'a, R> {
+    pub(crate) fn new(reader: R, key: &'a [u8]) -> Self {
+        XorReader {
+            reader,
+            key,
+            key_pos: 0,
+            buf: [0; BUF_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that any data buffered in this adapter is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for XorReader<'_, R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.pos == self.len {
+            let input = self.reader.fill_buf()?;
+            let len = input.len().min(BUF_SIZE);
+            self.buf[..len].copy_from_slice(&input[..len]);
+            self.reader.consume(len);
+            self.key_pos = xor(&mut self.buf[..len], self.key, self.key_pos);
+            self.pos = 0;
+            self.len = len;
+        }
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.len - self.pos);
+        self.pos += amount;
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+
+    #[test]
+    fn xor_round_trip() {
+        let mut buf = [0u8; 5];
+        let mut writer = (&mut buf[..]).xor_write(&[0x0F, 0xF0]);
+        writer.write_all(&[1, 2, 3]).unwrap();
+        writer.write_all(&[4, 5]).unwrap();
+        assert_eq!(buf, [0x0E, 0xF2, 0x0C, 0xF4, 0x0A]);
+
+        let mut reader = (&buf[..]).xor(&[0x0F, 0xF0]);
+        for expected in 1..=5 {
+            assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(expected));
+        }
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+    }
+
+    #[test]
+    fn xor_retry_after_failure() {
+        let mut buf = [0u8; 2];
+        let mut writer = (&mut buf[..]).xor_write(&[0x0F, 0xF0]);
+        writer.write_all(&[1]).unwrap();
+        assert!(writer.write_all(&[2, 3, 4]).is_err());
+        writer.write_all(&[2]).unwrap();
+        assert_eq!(buf, [0x0E, 0xF2]);
+    }
+}
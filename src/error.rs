@@ -41,6 +41,16 @@ impl UnexpectedEnd {
             available,
         }
     }
+
+    /// Returns the number of bytes that were required.
+    pub fn total_required(&self) -> usize {
+        self.total_required
+    }
+
+    /// Returns the number of bytes that were actually read before the end.
+    pub fn available(&self) -> usize {
+        self.available
+    }
 }
 
 impl fmt::Display for UnexpectedEnd {
@@ -74,6 +84,16 @@ impl<E> ReadExactError<E> {
             ReadExactError::ReadingFailed(error) => ReadExactError::ReadingFailed(f(error)),
         }
     }
+
+    /// Flattens the error into the read error.
+    ///
+    /// This is useful for decoders having a single error type that can represent unexpected end.
+    pub fn into_read_err(self) -> E where E: From<UnexpectedEnd> {
+        match self {
+            ReadExactError::UnexpectedEnd(error) => error.into(),
+            ReadExactError::ReadingFailed(error) => error,
+        }
+    }
 }
 
 impl ReadExactError<core::convert::Infallible> {
@@ -111,3 +131,978 @@ impl<E: std::error::Error + 'static> std::error::Error for ReadExactError<E> {
         }
     }
 }
+
+/// Error returned when a computed checksum doesn't match the expected one.
+#[derive(Debug, Clone)]
+pub struct ChecksumMismatch {
+    expected: u32,
+    computed: u32,
+}
+
+impl ChecksumMismatch {
+    /// Constructs the error.
+    pub fn new(expected: u32, computed: u32) -> Self {
+        ChecksumMismatch {
+            expected,
+            computed,
+        }
+    }
+
+    /// Returns the checksum that was stored in the stream.
+    pub fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Returns the checksum computed from the data.
+    pub fn computed(&self) -> u32 {
+        self.computed
+    }
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "checksum mismatch: expected {:08x}, computed {:08x}", self.expected, self.computed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChecksumMismatch {}
+
+/// Error returned from readers verifying a checksum, such as
+/// [`Crc32Reader`](crate::adapters::Crc32Reader).
+#[derive(Debug, Clone)]
+pub enum ChecksumReadError<E> {
+    /// The stream ended before the checksum trailer could be read.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The checksum doesn't match the data.
+    ChecksumMismatch(ChecksumMismatch),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<UnexpectedEnd> for ChecksumReadError<E> {
+    fn from(error: UnexpectedEnd) -> Self {
+        ChecksumReadError::UnexpectedEnd(error)
+    }
+}
+
+impl<E> From<ChecksumMismatch> for ChecksumReadError<E> {
+    fn from(error: ChecksumMismatch) -> Self {
+        ChecksumReadError::ChecksumMismatch(error)
+    }
+}
+
+impl<E> fmt::Display for ChecksumReadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChecksumReadError::UnexpectedEnd(_) => write!(f, "missing checksum"),
+            ChecksumReadError::ChecksumMismatch(_) => write!(f, "invalid checksum"),
+            ChecksumReadError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ChecksumReadError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChecksumReadError::UnexpectedEnd(error) => Some(error),
+            ChecksumReadError::ChecksumMismatch(error) => Some(error),
+            ChecksumReadError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned from [`BufWrite::write_all_counting`](crate::BufWrite::write_all_counting).
+///
+/// Apart from the underlying error it carries the number of bytes that were written before the
+/// failure.
+#[derive(Debug, Clone)]
+pub struct WriteAllError<E> {
+    written: usize,
+    error: E,
+}
+
+impl<E> WriteAllError<E> {
+    /// Constructs the error.
+    pub fn new(written: usize, error: E) -> Self {
+        WriteAllError {
+            written,
+            error,
+        }
+    }
+
+    /// Returns the number of bytes known to be written before the failure.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Returns the reference to the underlying error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the underlying error, discarding the number of written bytes.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+
+    /// Transforms the underlying error using the closure `f`.
+    pub fn map_err<E2, F: FnOnce(E) -> E2>(self, f: F) -> WriteAllError<E2> {
+        WriteAllError {
+            written: self.written,
+            error: f(self.error),
+        }
+    }
+}
+
+impl<E> fmt::Display for WriteAllError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "writing failed after {} bytes were written", self.written)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for WriteAllError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned when attempting to consume more bytes than are available in the buffer.
+#[derive(Debug, Clone)]
+pub struct ConsumeError {
+    requested: usize,
+    available: usize,
+}
+
+impl ConsumeError {
+    /// Constructs the error.
+    pub fn new(requested: usize, available: usize) -> Self {
+        ConsumeError {
+            requested,
+            available,
+        }
+    }
+}
+
+impl fmt::Display for ConsumeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "attempted to consume {} bytes but only {} bytes are available in the buffer", self.requested, self.available)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConsumeError {}
+
+/// Error returned when a configured limit on the number of bytes was exceeded.
+#[derive(Debug, Clone)]
+pub struct LimitExceeded {
+    limit: u64,
+}
+
+impl LimitExceeded {
+    /// Constructs the error.
+    pub fn new(limit: u64) -> Self {
+        LimitExceeded {
+            limit,
+        }
+    }
+
+    /// Returns the limit that was exceeded.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the limit of {} bytes was exceeded", self.limit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LimitExceeded {}
+
+/// Error returned from [`WriteTake`](crate::adapters::WriteTake).
+#[derive(Debug, Clone)]
+pub enum WriteTakeError<E> {
+    /// The write would exceed the limit.
+    LimitExceeded(LimitExceeded),
+    /// Writing failed.
+    WritingFailed(E),
+}
+
+impl<E> From<LimitExceeded> for WriteTakeError<E> {
+    fn from(error: LimitExceeded) -> Self {
+        WriteTakeError::LimitExceeded(error)
+    }
+}
+
+impl<E> fmt::Display for WriteTakeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WriteTakeError::LimitExceeded(_) => write!(f, "write limit exceeded"),
+            WriteTakeError::WritingFailed(_) => write!(f, "writing failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for WriteTakeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WriteTakeError::LimitExceeded(error) => Some(error),
+            WriteTakeError::WritingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned from [`BufRead::read_until_any`](crate::BufRead::read_until_any).
+#[derive(Debug, Clone)]
+pub enum ReadUntilError<E> {
+    /// No delimiter was found within the limit.
+    LimitExceeded(LimitExceeded),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<LimitExceeded> for ReadUntilError<E> {
+    fn from(error: LimitExceeded) -> Self {
+        ReadUntilError::LimitExceeded(error)
+    }
+}
+
+impl<E> fmt::Display for ReadUntilError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadUntilError::LimitExceeded(_) => write!(f, "delimiter not found"),
+            ReadUntilError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ReadUntilError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadUntilError::LimitExceeded(error) => Some(error),
+            ReadUntilError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned from [`BufRead::read_line`](crate::BufRead::read_line).
+#[derive(Debug, Clone)]
+pub enum ReadLineError<E> {
+    /// Reading failed.
+    ReadingFailed(E),
+    /// The line is not valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+}
+
+impl<E> From<core::str::Utf8Error> for ReadLineError<E> {
+    fn from(error: core::str::Utf8Error) -> Self {
+        ReadLineError::InvalidUtf8(error)
+    }
+}
+
+impl<E> fmt::Display for ReadLineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadLineError::ReadingFailed(_) => write!(f, "reading failed"),
+            ReadLineError::InvalidUtf8(_) => write!(f, "the line is not valid UTF-8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ReadLineError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadLineError::ReadingFailed(error) => Some(error),
+            ReadLineError::InvalidUtf8(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when decoding run-length encoded data - returned from
+/// [`RleReader`](crate::adapters::RleReader).
+#[derive(Debug, Clone)]
+pub enum RleError<E> {
+    /// A run has zero length.
+    ZeroCount,
+    /// The stream ended in the middle of a (count, byte) pair.
+    UnexpectedEnd(UnexpectedEnd),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<UnexpectedEnd> for RleError<E> {
+    fn from(error: UnexpectedEnd) -> Self {
+        RleError::UnexpectedEnd(error)
+    }
+}
+
+impl<E> fmt::Display for RleError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RleError::ZeroCount => write!(f, "invalid run length 0"),
+            RleError::UnexpectedEnd(_) => write!(f, "truncated run"),
+            RleError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for RleError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RleError::ZeroCount => None,
+            RleError::UnexpectedEnd(error) => Some(error),
+            RleError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when writing bytes that are supposed to be UTF-8 into a string.
+#[derive(Debug, Clone)]
+pub enum StrWriteError {
+    /// The string doesn't have enough capacity.
+    BufferOverflow(BufferOverflow),
+    /// The bytes are not valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+}
+
+impl From<BufferOverflow> for StrWriteError {
+    fn from(error: BufferOverflow) -> Self {
+        StrWriteError::BufferOverflow(error)
+    }
+}
+
+impl From<core::str::Utf8Error> for StrWriteError {
+    fn from(error: core::str::Utf8Error) -> Self {
+        StrWriteError::InvalidUtf8(error)
+    }
+}
+
+impl fmt::Display for StrWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StrWriteError::BufferOverflow(_) => write!(f, "the string is full"),
+            StrWriteError::InvalidUtf8(_) => write!(f, "invalid UTF-8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StrWriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StrWriteError::BufferOverflow(error) => Some(error),
+            StrWriteError::InvalidUtf8(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when one of multiple writers fails - returned from
+/// [`Multiplex`](crate::adapters::Multiplex).
+#[derive(Debug, Clone)]
+pub struct MultiplexError<E> {
+    index: usize,
+    error: E,
+}
+
+impl<E> MultiplexError<E> {
+    /// Constructs the error.
+    pub fn new(index: usize, error: E) -> Self {
+        MultiplexError {
+            index,
+            error,
+        }
+    }
+
+    /// Returns the index of the writer that failed.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the reference to the underlying error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the underlying error, discarding the index.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> fmt::Display for MultiplexError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "writer at index {} failed", self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for MultiplexError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned when reading length-prefixed frames - returned from
+/// [`LengthPrefixReader`](crate::adapters::LengthPrefixReader).
+#[derive(Debug, Clone)]
+pub enum LengthPrefixError<E> {
+    /// The length prefix doesn't fit into 64 bits.
+    InvalidLength,
+    /// The stream ended in the middle of a frame.
+    UnexpectedEnd(UnexpectedEnd),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<UnexpectedEnd> for LengthPrefixError<E> {
+    fn from(error: UnexpectedEnd) -> Self {
+        LengthPrefixError::UnexpectedEnd(error)
+    }
+}
+
+impl<E> fmt::Display for LengthPrefixError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LengthPrefixError::InvalidLength => write!(f, "the length prefix overflows 64 bits"),
+            LengthPrefixError::UnexpectedEnd(_) => write!(f, "truncated frame"),
+            LengthPrefixError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for LengthPrefixError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LengthPrefixError::InvalidLength => None,
+            LengthPrefixError::UnexpectedEnd(error) => Some(error),
+            LengthPrefixError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when reading bytes that are supposed to be UTF-8 - returned from
+/// [`Utf8Validator`](crate::adapters::Utf8Validator).
+#[derive(Debug, Clone)]
+pub enum Utf8ReadError<E> {
+    /// The bytes are not valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<core::str::Utf8Error> for Utf8ReadError<E> {
+    fn from(error: core::str::Utf8Error) -> Self {
+        Utf8ReadError::InvalidUtf8(error)
+    }
+}
+
+impl<E> fmt::Display for Utf8ReadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Utf8ReadError::InvalidUtf8(_) => write!(f, "invalid UTF-8"),
+            Utf8ReadError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for Utf8ReadError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Utf8ReadError::InvalidUtf8(error) => Some(error),
+            Utf8ReadError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when a line is longer than allowed.
+#[derive(Debug, Clone)]
+pub struct LineTooLong {
+    max: usize,
+}
+
+impl LineTooLong {
+    /// Constructs the error.
+    pub fn new(max: usize) -> Self {
+        LineTooLong {
+            max,
+        }
+    }
+
+    /// Returns the maximum allowed length of a line.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for LineTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the line is longer than {} bytes", self.max)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LineTooLong {}
+
+/// Error returned from [`LineLengthLimit`](crate::adapters::LineLengthLimit).
+#[derive(Debug, Clone)]
+pub enum LineLengthError<E> {
+    /// The line is too long.
+    LineTooLong(LineTooLong),
+    /// Writing failed.
+    WritingFailed(E),
+}
+
+impl<E> From<LineTooLong> for LineLengthError<E> {
+    fn from(error: LineTooLong) -> Self {
+        LineLengthError::LineTooLong(error)
+    }
+}
+
+impl<E> fmt::Display for LineLengthError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LineLengthError::LineTooLong(_) => write!(f, "line too long"),
+            LineLengthError::WritingFailed(_) => write!(f, "writing failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for LineLengthError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LineLengthError::LineTooLong(error) => Some(error),
+            LineLengthError::WritingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned from [`PadToWriter`](crate::adapters::PadToWriter).
+#[derive(Debug, Clone)]
+pub enum PadToError<E> {
+    /// The written bytes exceed the total length.
+    TooLong(BufferOverflow),
+    /// Writing failed.
+    WritingFailed(E),
+}
+
+impl<E> From<BufferOverflow> for PadToError<E> {
+    fn from(error: BufferOverflow) -> Self {
+        PadToError::TooLong(error)
+    }
+}
+
+impl<E> fmt::Display for PadToError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PadToError::TooLong(_) => write!(f, "the content is longer than the padded length"),
+            PadToError::WritingFailed(_) => write!(f, "writing failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for PadToError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PadToError::TooLong(error) => Some(error),
+            PadToError::WritingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when un-escaping JSON string - returned from
+/// [`JsonStringUnescapeReader`](crate::adapters::JsonStringUnescapeReader).
+#[derive(Debug, Clone)]
+pub enum JsonUnescapeError<E> {
+    /// The byte following `\` is not a valid escape.
+    InvalidEscape(u8),
+    /// The `\u` escape is not followed by four hex digits.
+    InvalidUnicodeEscape,
+    /// The `\u` escape encodes a surrogate that is not a part of a valid pair.
+    LoneSurrogate(u16),
+    /// The stream ended in the middle of an escape sequence.
+    UnexpectedEnd(UnexpectedEnd),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<UnexpectedEnd> for JsonUnescapeError<E> {
+    fn from(error: UnexpectedEnd) -> Self {
+        JsonUnescapeError::UnexpectedEnd(error)
+    }
+}
+
+impl<E> fmt::Display for JsonUnescapeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonUnescapeError::InvalidEscape(byte) => write!(f, "invalid escape sequence \\{}", core::ascii::escape_default(*byte)),
+            JsonUnescapeError::InvalidUnicodeEscape => write!(f, "invalid unicode escape sequence"),
+            JsonUnescapeError::LoneSurrogate(surrogate) => write!(f, "lone surrogate \\u{:04x}", surrogate),
+            JsonUnescapeError::UnexpectedEnd(_) => write!(f, "truncated escape sequence"),
+            JsonUnescapeError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for JsonUnescapeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonUnescapeError::InvalidEscape(_) => None,
+            JsonUnescapeError::InvalidUnicodeEscape => None,
+            JsonUnescapeError::LoneSurrogate(_) => None,
+            JsonUnescapeError::UnexpectedEnd(error) => Some(error),
+            JsonUnescapeError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned from [`BufRead::read_exact_to`](crate::BufRead::read_exact_to).
+#[derive(Debug, Clone)]
+pub enum CopyExactError<R, W> {
+    /// The reader ended before the required number of bytes was copied.
+    UnexpectedEnd(UnexpectedEnd),
+    /// Reading or writing failed.
+    CopyFailed(CopyError<R, W>),
+}
+
+impl<R, W> From<UnexpectedEnd> for CopyExactError<R, W> {
+    fn from(error: UnexpectedEnd) -> Self {
+        CopyExactError::UnexpectedEnd(error)
+    }
+}
+
+impl<R, W> From<CopyError<R, W>> for CopyExactError<R, W> {
+    fn from(error: CopyError<R, W>) -> Self {
+        CopyExactError::CopyFailed(error)
+    }
+}
+
+impl<R, W> fmt::Display for CopyExactError<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopyExactError::UnexpectedEnd(_) => write!(f, "unexpected end of stream"),
+            CopyExactError::CopyFailed(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::error::Error + 'static, W: std::error::Error + 'static> std::error::Error for CopyExactError<R, W> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CopyExactError::UnexpectedEnd(error) => Some(error),
+            // the message is already displayed
+            CopyExactError::CopyFailed(error) => error.source(),
+        }
+    }
+}
+
+/// Error returned from [`copy`](crate::copy).
+#[derive(Debug, Clone)]
+pub enum CopyError<R, W> {
+    /// Reading failed.
+    ReadingFailed(R),
+    /// Writing failed.
+    WritingFailed(W),
+}
+
+impl<R, W> fmt::Display for CopyError<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopyError::ReadingFailed(_) => write!(f, "reading failed"),
+            CopyError::WritingFailed(_) => write!(f, "writing failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::error::Error + 'static, W: std::error::Error + 'static> std::error::Error for CopyError<R, W> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CopyError::ReadingFailed(error) => Some(error),
+            CopyError::WritingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Converts the error keeping its [`ErrorKind`](std::io::ErrorKind).
+///
+/// The message of the returned error states whether reading or writing failed and the original
+/// error is available as its source.
+#[cfg(feature = "std")]
+impl<R: Into<std::io::Error>, W: Into<std::io::Error>> From<CopyError<R, W>> for std::io::Error {
+    fn from(error: CopyError<R, W>) -> Self {
+        let (side, error) = match error {
+            CopyError::ReadingFailed(error) => ("reading", error.into()),
+            CopyError::WritingFailed(error) => ("writing", error.into()),
+        };
+        std::io::Error::new(error.kind(), CopySideError { side, error })
+    }
+}
+
+/// Wraps the error of one side of the copy to mention the side in the message.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct CopySideError {
+    side: &'static str,
+    error: std::io::Error,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CopySideError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} failed", self.side)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CopySideError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned when reading tagged frames - returned from
+/// [`Demux::next_frame`](crate::adapters::Demux::next_frame).
+#[derive(Debug, Clone)]
+pub enum FrameError<E> {
+    /// The length prefix doesn't fit into 64 bits.
+    InvalidLength,
+    /// The frame is longer than the configured maximum, contains the declared length.
+    TooLong(u64),
+    /// The stream ended in the middle of a frame.
+    UnexpectedEnd(UnexpectedEnd),
+    /// Reading failed.
+    ReadingFailed(E),
+}
+
+impl<E> From<UnexpectedEnd> for FrameError<E> {
+    fn from(error: UnexpectedEnd) -> Self {
+        FrameError::UnexpectedEnd(error)
+    }
+}
+
+impl<E> From<LengthPrefixError<E>> for FrameError<E> {
+    fn from(error: LengthPrefixError<E>) -> Self {
+        match error {
+            LengthPrefixError::InvalidLength => FrameError::InvalidLength,
+            LengthPrefixError::UnexpectedEnd(error) => FrameError::UnexpectedEnd(error),
+            LengthPrefixError::ReadingFailed(error) => FrameError::ReadingFailed(error),
+        }
+    }
+}
+
+impl<E> From<ReadExactError<E>> for FrameError<E> {
+    fn from(error: ReadExactError<E>) -> Self {
+        match error {
+            ReadExactError::UnexpectedEnd(error) => FrameError::UnexpectedEnd(error),
+            ReadExactError::ReadingFailed(error) => FrameError::ReadingFailed(error),
+        }
+    }
+}
+
+impl<E> fmt::Display for FrameError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrameError::InvalidLength => write!(f, "the length prefix overflows 64 bits"),
+            FrameError::TooLong(len) => write!(f, "the frame length {} exceeds the maximum", len),
+            FrameError::UnexpectedEnd(_) => write!(f, "truncated frame"),
+            FrameError::ReadingFailed(_) => write!(f, "reading failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for FrameError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FrameError::InvalidLength | FrameError::TooLong(_) => None,
+            FrameError::UnexpectedEnd(error) => Some(error),
+            FrameError::ReadingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when filling a placeholder - returned from
+/// [`BackfillWriter::fill`](crate::adapters::BackfillWriter::fill).
+#[derive(Debug, Clone)]
+pub enum BackfillError<W, S> {
+    /// Writing failed.
+    WritingFailed(W),
+    /// Seeking failed.
+    SeekingFailed(S),
+}
+
+impl<W, S> fmt::Display for BackfillError<W, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackfillError::WritingFailed(_) => write!(f, "writing failed"),
+            BackfillError::SeekingFailed(_) => write!(f, "seeking failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::error::Error + 'static, S: std::error::Error + 'static> std::error::Error for BackfillError<W, S> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BackfillError::WritingFailed(error) => Some(error),
+            BackfillError::SeekingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when the number of written bytes doesn't match the expected size - returned
+/// from [`ExactSizeWriter`](crate::adapters::ExactSizeWriter).
+#[derive(Debug, Clone)]
+pub enum ExactSizeError<E> {
+    /// Fewer bytes than expected were written, contains the number of missing bytes.
+    Short(u64),
+    /// The write would exceed the expected size.
+    Overflow(BufferOverflow),
+    /// Writing failed.
+    WritingFailed(E),
+}
+
+impl<E> From<BufferOverflow> for ExactSizeError<E> {
+    fn from(error: BufferOverflow) -> Self {
+        ExactSizeError::Overflow(error)
+    }
+}
+
+impl<E> fmt::Display for ExactSizeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExactSizeError::Short(missing) => write!(f, "{} bytes fewer than expected were written", missing),
+            ExactSizeError::Overflow(_) => write!(f, "more bytes than expected were written"),
+            ExactSizeError::WritingFailed(_) => write!(f, "writing failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ExactSizeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExactSizeError::Short(_) => None,
+            ExactSizeError::Overflow(error) => Some(error),
+            ExactSizeError::WritingFailed(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when reading through [`Tee`](crate::adapters::Tee).
+#[derive(Debug, Clone)]
+pub enum TeeError<R, W> {
+    /// Reading failed.
+    ReadingFailed(R),
+    /// Writing the consumed bytes failed.
+    WritingFailed(W),
+}
+
+impl<R, W> fmt::Display for TeeError<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TeeError::ReadingFailed(_) => write!(f, "reading failed"),
+            TeeError::WritingFailed(_) => write!(f, "writing consumed bytes failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::error::Error + 'static, W: std::error::Error + 'static> std::error::Error for TeeError<R, W> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TeeError::ReadingFailed(error) => Some(error),
+            TeeError::WritingFailed(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+    use std::error::Error;
+    use std::string::ToString;
+    use super::{CopyError, CopyExactError, LimitExceeded, LineLengthError, LineTooLong, ReadLineError, WriteTakeError};
+
+    #[test]
+    fn copy_error_into_io() {
+        let error = CopyError::<io::Error, io::Error>::WritingFailed(io::ErrorKind::BrokenPipe.into());
+        let error = io::Error::from(error);
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(error.to_string(), "writing failed");
+        assert!(error.get_ref().unwrap().source().unwrap().is::<io::Error>());
+
+        let error = CopyError::<io::Error, io::Error>::ReadingFailed(io::ErrorKind::TimedOut.into());
+        let error = io::Error::from(error);
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(error.to_string(), "reading failed");
+    }
+
+    #[test]
+    fn sources() {
+        let error = ReadLineError::ReadingFailed(io::Error::from(io::ErrorKind::Other));
+        assert!(error.source().unwrap().is::<io::Error>());
+        let mut line = std::string::String::new();
+        let error = crate::BufRead::read_line(&mut &[0xff, b'\n'][..], &mut line).unwrap_err();
+        assert!(error.source().unwrap().is::<core::str::Utf8Error>());
+
+        let error = CopyError::<io::Error, io::Error>::WritingFailed(io::ErrorKind::Other.into());
+        assert!(error.source().unwrap().is::<io::Error>());
+        let error = CopyExactError::from(error);
+        assert_eq!(error.to_string(), "writing failed");
+        assert!(error.source().unwrap().is::<io::Error>());
+        let error = CopyExactError::<io::Error, io::Error>::from(super::UnexpectedEnd::new(4, 2));
+        assert_eq!(error.to_string(), "unexpected end of stream");
+        assert!(error.source().unwrap().is::<super::UnexpectedEnd>());
+
+        let error = WriteTakeError::<io::Error>::LimitExceeded(LimitExceeded::new(4));
+        assert_eq!(error.to_string(), "write limit exceeded");
+        assert!(error.source().unwrap().is::<LimitExceeded>());
+        assert!(LimitExceeded::new(4).source().is_none());
+
+        let error = LineLengthError::<io::Error>::LineTooLong(LineTooLong::new(80));
+        assert_eq!(error.to_string(), "line too long");
+        assert!(error.source().unwrap().is::<LineTooLong>());
+    }
+
+    #[test]
+    fn flatten_read_exact_error() {
+        use super::{ReadExactError, UnexpectedEnd};
+
+        #[derive(Debug)]
+        enum DecodeError {
+            Io(io::Error),
+            Truncated(UnexpectedEnd),
+        }
+
+        impl From<UnexpectedEnd> for DecodeError {
+            fn from(error: UnexpectedEnd) -> Self {
+                DecodeError::Truncated(error)
+            }
+        }
+
+        match ReadExactError::<DecodeError>::unexpected_end(4, 2).into_read_err() {
+            DecodeError::Truncated(error) => assert_eq!(error.available(), 2),
+            DecodeError::Io(error) => panic!("unexpected IO error {}", error),
+        }
+        match ReadExactError::ReadingFailed(DecodeError::Io(io::ErrorKind::Other.into())).into_read_err() {
+            DecodeError::Io(error) => assert_eq!(error.kind(), io::ErrorKind::Other),
+            DecodeError::Truncated(error) => panic!("unexpected end {}", error),
+        }
+    }
+}
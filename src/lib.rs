@@ -15,7 +15,8 @@
 //! ## Advantages over `genio`
 //!
 //! * Simpler
-//! * Less `unsafe` to deal with uninitialized bytes (currently none, may change in the future)
+//! * Less `unsafe` to deal with uninitialized bytes (only a few small internal casts, checked by
+//!   running the tests under Miri)
 //! * Most uses of IO need some buffering anyway
 //! * Less error-prone - no `read` and `write` methods which are often mistaken for `read_all` or
 //!   `write_all`
@@ -45,16 +46,24 @@
 //!
 //! * `std` - integration with the standard library: implementations and adapters
 //! * `alloc` - additional features requiring allocation
+//! * `heapless` - integration with the [`heapless`](https://docs.rs/heapless) crate
+//! * `test-utils` - helpers for testing decoders and custom implementations of the traits
 //!
 //! ## MSRV
 //!
 //! The crate intends to have conservative MSRV and only bump it when it provides significant
 //! benefit and at most to the version available in latest Debian stable. Currently tested MSRV is
-//! 1.41.1 (Debian oldstable) but due to its simplicity it's possible it works on even lower
-//! versions.
+//! 1.63 (Debian bookworm). It was bumped from 1.41.1 to make array-backed buffers generic over
+//! their size (const generics) and to compute checksum tables at compile time.
 //!
 //! Some features may be only available in newer Rust versions. Thus it is recommended to use
 //! recent Rust if possible.
+//!
+//! ## Testing
+//!
+//! The `unsafe` code is covered by the regular tests which should also pass under
+//! [Miri](https://github.com/rust-lang/miri): `cargo +nightly miri test --all-features`. Tests
+//! accessing the file system are skipped there.
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
@@ -67,11 +76,60 @@ extern crate alloc;
 
 pub mod adapters;
 pub mod error;
+#[cfg(feature = "test-utils")]
+pub mod testing;
 mod sync_impls;
 
+use core::convert::TryFrom;
 use adapters::*;
 use error::*;
 
+/// Generates methods reading integers of fixed endianness.
+macro_rules! read_int_methods {
+    ($($ty:ident: $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Reads little-endian `", stringify!($ty), "`.")]
+            ///
+            /// # Errors
+            ///
+            /// The errors are the same as in the case of [`read_exact`](Self::read_exact).
+            fn $le(&mut self) -> Result<$ty, ReadExactError<Self::ReadError>> {
+                let mut buf = [0u8; core::mem::size_of::<$ty>()];
+                self.read_exact(&mut buf)?;
+                Ok($ty::from_le_bytes(buf))
+            }
+
+            #[doc = concat!("Reads big-endian `", stringify!($ty), "`.")]
+            ///
+            /// # Errors
+            ///
+            /// The errors are the same as in the case of [`read_exact`](Self::read_exact).
+            fn $be(&mut self) -> Result<$ty, ReadExactError<Self::ReadError>> {
+                let mut buf = [0u8; core::mem::size_of::<$ty>()];
+                self.read_exact(&mut buf)?;
+                Ok($ty::from_be_bytes(buf))
+            }
+        )*
+    };
+}
+
+/// Generates methods writing integers of fixed endianness.
+macro_rules! write_int_methods {
+    ($($ty:ident: $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Writes `", stringify!($ty), "` in little endian.")]
+            fn $le(&mut self, value: $ty) -> Result<(), Self::WriteError> {
+                self.write_all(&value.to_le_bytes())
+            }
+
+            #[doc = concat!("Writes `", stringify!($ty), "` in big endian.")]
+            fn $be(&mut self, value: $ty) -> Result<(), Self::WriteError> {
+                self.write_all(&value.to_be_bytes())
+            }
+        )*
+    };
+}
+
 /// A `BufRead` is a reader which has an internal buffer, allowing it to perform reading
 /// efficiently.
 ///
@@ -95,7 +153,10 @@ pub trait BufRead {
     /// must be called with the number of bytes that are consumed from this buffer to ensure that
     /// the bytes are never returned twice.
     ///
-    /// An empty buffer returned indicates that the stream has reached end (EOF).
+    /// An empty buffer returned indicates that the stream has reached end (EOF). For in-memory
+    /// sources that may be refilled later (e.g. a queue shared with a producer) this only means
+    /// there's no data available at the moment. Such readers should be drained using
+    /// [`drain_available`](Self::drain_available) rather than [`read_to_end`](Self::read_to_end).
     ///
     /// Note that implementors should handle errors that correspond to
     /// [`std::io::ErrorKind::Interrupted`] and restart the operation. All `std` adapters in this
@@ -125,7 +186,65 @@ pub trait BufRead {
     /// [`fill_buf`]: Self::fill_buf
     fn consume(&mut self, amount: usize);
 
+    /// Returns the contents of the internal buffer without performing any I/O.
+    ///
+    /// Unlike [`fill_buf`](Self::fill_buf), this never reads from the underlying source so the
+    /// returned buffer may be empty even if the stream didn't end. It's useful for peeking at
+    /// data that is already available.
+    ///
+    /// The default implementation returns an empty slice.
+    fn buffer(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the bounds on the number of bytes remaining in the stream.
+    ///
+    /// This has the same semantics as [`Iterator::size_hint`]: the first element is the lower
+    /// bound, the second one is the upper bound with `None` meaning unknown (or too large for
+    /// `usize`). It's used to pre-allocate memory, e.g. in [`read_to_end`](Self::read_to_end).
+    /// Buggy implementations must not cause memory unsafety but may cause incorrect allocation.
+    ///
+    /// The default implementation returns `(0, None)`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    /// Returns the number of bytes consumed from the start of the stream if it's known.
+    ///
+    /// This is useful for reporting positions in error messages without wrapping the reader in
+    /// a counting adapter. Readers that don't track their position return `None`, which is the
+    /// default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = lgio::read_str("hello");
+    /// reader.consume(2);
+    /// assert_eq!(reader.stream_position_hint(), Some(2));
+    /// ```
+    fn stream_position_hint(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the next byte without consuming it.
+    ///
+    /// Repeated calls return the same byte until it's consumed. This is useful for tokenizers
+    /// deciding what to parse next.
+    ///
+    /// # Errors
+    ///
+    /// * Returns `Err` if reading fails.
+    /// * Returns `Ok(None)` if there are no more bytes.
+    fn peek_byte(&mut self) -> Result<Option<u8>, Self::ReadError> {
+        Ok(self.fill_buf()?.first().copied())
+    }
+
     /// Reads a single byte from the reader.
+    ///
+    /// Each call performs at most one `consume(1)` - only if a byte was returned. At the end of
+    /// the stream `consume` is not called at all.
     /// 
     /// # Errors
     ///
@@ -145,8 +264,8 @@ pub trait BufRead {
     /// recommended that implementations only write data to `buf` and do not read its contents.
     ///
     /// Note that this method doesn't allow skipping initialization of the buffer which may lead to
-    /// decreased performance. Consider reading the bytes off the buffer returned by `fill_buf`
-    /// instead.
+    /// decreased performance. Consider using [`read_exact_uninit`](Self::read_exact_uninit) or
+    /// reading the bytes off the buffer returned by `fill_buf` instead.
     ///
     /// # Errors
     ///
@@ -164,16 +283,260 @@ pub trait BufRead {
         while !buf.is_empty() {
             let read = self.fill_buf().map_err(ReadExactError::ReadingFailed)?;
             if read.is_empty() {
-                return Err(ReadExactError::unexpected_end(required, buf.len()));
+                return Err(ReadExactError::unexpected_end(required, required - buf.len()));
             }
             let to_copy = buf.len().min(read.len());
             let (target, remaining) = buf.split_at_mut(to_copy);
             target.copy_from_slice(&read[..to_copy]);
+            self.consume(to_copy);
             buf = remaining;
         }
         Ok(())
     }
 
+    /// Reads the exact number of bytes required to fill `buf` and returns it.
+    ///
+    /// This is the same as [`read_exact`](Self::read_exact) but it takes the buffer by value,
+    /// which is convenient for arrays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &[1, 2, 3][..];
+    /// let bytes = reader.read_exact_into([0u8; 2]).unwrap();
+    /// assert_eq!(bytes, [1, 2]);
+    /// ```
+    fn read_exact_into<B: AsMut<[u8]>>(&mut self, mut buf: B) -> Result<B, ReadExactError<Self::ReadError>> where Self: Sized {
+        self.read_exact(buf.as_mut())?;
+        Ok(buf)
+    }
+
+    /// Copies exactly `n` bytes from this reader into `dst`.
+    ///
+    /// The bytes are passed from the buffer of this reader directly to
+    /// [`write_all`](BufWrite::write_all) without an intermediate buffer. This is useful for
+    /// forwarding payloads of known size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CopyExactError::UnexpectedEnd`] if the reader ends before `n` bytes were copied
+    /// or [`CopyExactError::CopyFailed`] if reading or writing fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &[1, 2, 3][..];
+    /// let mut buf = [0u8; 2];
+    /// reader.read_exact_to(2, &mut &mut buf[..]).unwrap();
+    /// assert_eq!(buf, [1, 2]);
+    /// assert_eq!(reader, [3]);
+    /// ```
+    fn read_exact_to<W: BufWrite + ?Sized>(&mut self, n: u64, dst: &mut W) -> Result<(), CopyExactError<Self::ReadError, W::WriteError>> where Self: Sized {
+        let mut remaining = n;
+        while remaining > 0 {
+            let buf = self.fill_buf().map_err(CopyError::ReadingFailed)?;
+            if buf.is_empty() {
+                let total_required = usize::try_from(n).unwrap_or(usize::MAX);
+                let available = usize::try_from(n - remaining).unwrap_or(usize::MAX);
+                return Err(UnexpectedEnd::new(total_required, available).into());
+            }
+            let len = usize::try_from(remaining).map_or(buf.len(), |remaining| remaining.min(buf.len()));
+            dst.write_all(&buf[..len]).map_err(CopyError::WritingFailed)?;
+            self.consume(len);
+            remaining -= len as u64;
+        }
+        Ok(())
+    }
+
+    /// Read the exact number of bytes required to fill uninitialized `buf`.
+    ///
+    /// This is the same as [`read_exact`](Self::read_exact) except it avoids the need to
+    /// initialize the buffer before reading into it. On success the returned slice is `buf`
+    /// which is now initialized.
+    ///
+    /// # Errors
+    ///
+    /// The errors are the same as in the case of `read_exact`. The contents of `buf` are
+    /// unspecified (possibly partially initialized) if an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut buf = [MaybeUninit::<u8>::uninit(); 2];
+    /// let bytes = (&[1, 2, 3][..]).read_exact_uninit(&mut buf).unwrap();
+    /// assert_eq!(bytes, [1, 2]);
+    /// ```
+    fn read_exact_uninit<'a>(&mut self, buf: &'a mut [core::mem::MaybeUninit<u8>]) -> Result<&'a mut [u8], ReadExactError<Self::ReadError>> {
+        read_exact_uninit(self, buf)
+    }
+
+    /// Reads exactly `n` bytes and appends them to `vec` without initializing them first.
+    ///
+    /// This is useful for reading frames into a reused vector, avoiding the cost of zeroing
+    /// the bytes using `resize`.
+    ///
+    /// # Errors
+    ///
+    /// The errors are the same as in the case of [`read_exact`](Self::read_exact). The length of
+    /// `vec` is unchanged if an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut vec = vec![42];
+    /// (&[1, 2, 3][..]).read_exact_into_spare(&mut vec, 2).unwrap();
+    /// assert_eq!(vec, [42, 1, 2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_exact_into_spare(&mut self, vec: &mut alloc::vec::Vec<u8>, n: usize) -> Result<(), ReadExactError<Self::ReadError>> {
+        vec.reserve(n);
+        // The private function is used instead of the trait method because an overridden
+        // method could return without initializing the bytes.
+        read_exact_uninit(self, &mut vec.spare_capacity_mut()[..n])?;
+        // SAFETY: read_exact_uninit initialized n bytes after the length
+        unsafe {
+            vec.set_len(vec.len() + n);
+        }
+        Ok(())
+    }
+
+    /// Reads exactly `n` bytes into a newly allocated vector.
+    ///
+    /// This is a faster and clearer alternative to `vec![0; n]` followed by
+    /// [`read_exact`](Self::read_exact) since the bytes are not zeroed first.
+    ///
+    /// # Errors
+    ///
+    /// The errors are the same as in the case of [`read_exact`](Self::read_exact). The partially
+    /// read bytes are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &[1, 2, 3][..];
+    /// assert_eq!(reader.read_vec_exact(2).unwrap(), [1, 2]);
+    /// assert!(reader.read_vec_exact(2).is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_vec_exact(&mut self, n: usize) -> Result<alloc::vec::Vec<u8>, ReadExactError<Self::ReadError>> {
+        let mut vec = alloc::vec::Vec::with_capacity(n);
+        self.read_exact_into_spare(&mut vec, n)?;
+        Ok(vec)
+    }
+
+    /// Read the exact number of bytes required to fill `buf` from a reader that can not fail.
+    ///
+    /// This is the same as [`read_exact`](Self::read_exact) but since reading can not fail the
+    /// only possible error is [`UnexpectedEnd`]. This is convenient when parsing in-memory
+    /// data such as `&[u8]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut buf = [0; 2];
+    /// (&[1, 2, 3][..]).read_exact_infallible(&mut buf).unwrap();
+    /// assert_eq!(buf, [1, 2]);
+    /// assert!((&[1][..]).read_exact_infallible(&mut buf).is_err());
+    /// ```
+    fn read_exact_infallible(&mut self, buf: &mut [u8]) -> Result<(), UnexpectedEnd> where Self::ReadError: Into<core::convert::Infallible> {
+        self.read_exact(buf).map_err(|error| match error {
+            ReadExactError::UnexpectedEnd(error) => error,
+            ReadExactError::ReadingFailed(error) => match error.into() {},
+        })
+    }
+
+    /// Reads as many bytes as possible into `buf`.
+    ///
+    /// Unlike [`read_exact`](Self::read_exact) this doesn't fail if the stream ends before `buf`
+    /// is filled. Returns the number of bytes read which is less than `buf.len()` only if the end
+    /// of the stream was reached. This is useful for reading whole small streams without `alloc`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error if reading fails. The bytes read before the error are lost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut buf = [0; 4];
+    /// let len = (&[1, 2][..]).read_into_slice(&mut buf).unwrap_or_else(|error| match error {});
+    /// assert_eq!(&buf[..len], [1, 2]);
+    /// ```
+    fn read_into_slice(&mut self, buf: &mut [u8]) -> Result<usize, Self::ReadError> {
+        let mut total = 0;
+        while total < buf.len() {
+            let read = self.fill_buf()?;
+            if read.is_empty() {
+                break;
+            }
+            let len = read.len().min(buf.len() - total);
+            buf[total..(total + len)].copy_from_slice(&read[..len]);
+            self.consume(len);
+            total += len;
+        }
+        Ok(total)
+    }
+
+    /// Discards exactly `amount` bytes.
+    ///
+    /// This only calls `fill_buf` and `consume` without copying the bytes anywhere so it's more
+    /// efficient than reading into a throwaway buffer. Useful for skipping padding or unknown
+    /// fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadExactError::UnexpectedEnd`] if the stream ends before `amount` bytes were
+    /// skipped or the error if reading fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &[1, 2, 3][..];
+    /// reader.skip(2).unwrap();
+    /// assert_eq!(reader, [3]);
+    /// ```
+    fn skip(&mut self, mut amount: u64) -> Result<(), ReadExactError<Self::ReadError>> {
+        let required = amount;
+        while amount > 0 {
+            let read = self.fill_buf().map_err(ReadExactError::ReadingFailed)?;
+            if read.is_empty() {
+                let total_required = usize::try_from(required).unwrap_or(usize::MAX);
+                let available = usize::try_from(required - amount).unwrap_or(usize::MAX);
+                return Err(ReadExactError::unexpected_end(total_required, available));
+            }
+            let len = usize::try_from(amount).map_or(read.len(), |amount| amount.min(read.len()));
+            self.consume(len);
+            amount -= len as u64;
+        }
+        Ok(())
+    }
+
+    read_int_methods! {
+        u16: read_u16_le, read_u16_be;
+        u32: read_u32_le, read_u32_be;
+        u64: read_u64_le, read_u64_be;
+        i16: read_i16_le, read_i16_be;
+        i32: read_i32_le, read_i32_be;
+        i64: read_i64_le, read_i64_be;
+    }
+
     /// Read all bytes until EOF in this source, placing them into `buf`.
     ///
     /// All bytes read from this source will be appended to the specified buffer
@@ -182,10 +545,20 @@ pub trait BufRead {
     ///
     /// If successful, this function will return the total number of bytes read.
     ///
+    /// The first empty buffer is treated as the final end of the stream. If the source can be
+    /// refilled later the function returns too early from the point of view of the consumer of
+    /// the whole stream - use [`drain_available`](Self::drain_available) in such cases.
+    ///
     /// # Errors
     ///
     /// If any read error is encountered then this function immediately returns. Any bytes 
     /// which have already been read will be appended to `buf`.
+    ///
+    /// # Panics
+    ///
+    /// With debug assertions enabled this panics if [`consume`] doesn't advance the reader
+    /// instead of looping forever. This is detected using [`buffer`](Self::buffer) and exact
+    /// [`size_hint`](Self::size_hint) so only implementations providing them are checked.
     /// 
     /// # Example
     ///
@@ -204,12 +577,197 @@ pub trait BufRead {
     /// [`Ok(0)`]: Ok
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
+        buf.reserve(self.size_hint().0);
+        let mut total = 0;
+        loop {
+            let read = self.fill_buf()?;
+            if read.is_empty() {
+                break Ok(total);
+            }
+            buf.extend_from_slice(read);
+            let len = read.len();
+            #[cfg(debug_assertions)]
+            let before = (read.as_ptr(), self.size_hint());
+            total += len;
+            self.consume(len);
+            // Catches buggy implementations of consume that would otherwise loop forever.
+            #[cfg(debug_assertions)]
+            {
+                let (ptr, size_hint) = before;
+                let buffer = self.buffer();
+                let stuck_buffer = buffer.as_ptr() == ptr && buffer.len() == len;
+                let stuck_hint = size_hint.1 == Some(size_hint.0) && self.size_hint() == size_hint;
+                if stuck_buffer || stuck_hint {
+                    panic!("BufRead::consume({}) did not advance the reader", len);
+                }
+            }
+        }
+    }
+
+    /// Reads bytes into `buf` until the `delim` byte is found.
+    ///
+    /// All bytes up to and including the delimiter are appended to `buf`. If the end of the
+    /// stream is reached before the delimiter is found the bytes read so far are appended.
+    /// Returns the number of bytes appended, zero means the reader was already at the end.
+    ///
+    /// Unlike [`read_until_any`](Self::read_until_any) the amount of read bytes is not limited
+    /// so this should not be used with untrusted input.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error if reading fails. Bytes read before the error are appended to `buf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &b"foo;bar"[..];
+    /// let mut buf = Vec::new();
+    /// assert_eq!(reader.read_until(b';', &mut buf).unwrap_or_else(|error| match error {}), 4);
+    /// assert_eq!(buf, b"foo;");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_until(&mut self, delim: u8, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
         let mut total = 0;
         loop {
             let read = self.fill_buf()?;
             if read.is_empty() {
                 break Ok(total);
             }
+            match read.iter().position(|&byte| byte == delim) {
+                Some(pos) => {
+                    buf.extend_from_slice(&read[..=pos]);
+                    self.consume(pos + 1);
+                    break Ok(total + pos + 1);
+                },
+                None => {
+                    buf.extend_from_slice(read);
+                    let len = read.len();
+                    total += len;
+                    self.consume(len);
+                },
+            }
+        }
+    }
+
+    /// Reads a line and appends it to `buf`.
+    ///
+    /// This reads bytes up to and including `\n` using [`read_until`](Self::read_until),
+    /// validates them and appends them to `buf`. If the stream ends without a trailing newline
+    /// the partial line is appended. Returns the number of bytes appended, zero means the reader
+    /// was already at the end.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error if reading fails or the line is not valid UTF-8. `buf` is left
+    /// unchanged in both cases and the bytes consumed by this call are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &b"hello\nworld"[..];
+    /// let mut line = String::new();
+    /// assert_eq!(reader.read_line(&mut line).unwrap(), 6);
+    /// assert_eq!(line, "hello\n");
+    /// line.clear();
+    /// assert_eq!(reader.read_line(&mut line).unwrap(), 5);
+    /// assert_eq!(line, "world");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_line(&mut self, buf: &mut alloc::string::String) -> Result<usize, ReadLineError<Self::ReadError>> {
+        let mut line = alloc::vec::Vec::new();
+        let len = self.read_until(b'\n', &mut line).map_err(ReadLineError::ReadingFailed)?;
+        buf.push_str(core::str::from_utf8(&line)?);
+        Ok(len)
+    }
+
+    /// Reads bytes into `buf` until any of the `delims` bytes is found.
+    ///
+    /// All bytes up to and including the delimiter are appended to `buf` and the delimiter that
+    /// was found is returned. If the end of the stream is reached before a delimiter is found
+    /// `Ok(None)` is returned and the bytes read so far are in `buf`.
+    ///
+    /// At most `max` bytes (including the delimiter) are appended to `buf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadUntilError::LimitExceeded`] if no delimiter was found within `max` bytes or
+    /// [`ReadUntilError::ReadingFailed`] if reading fails. Bytes read before the error are
+    /// appended to `buf` in both cases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufRead;
+    ///
+    /// let mut reader = &b"foo bar\tbaz"[..];
+    /// let mut buf = Vec::new();
+    /// assert_eq!(reader.read_until_any(b" \t\n", &mut buf, 16).unwrap(), Some(b' '));
+    /// assert_eq!(buf, b"foo ");
+    /// assert_eq!(reader.read_until_any(b" \t\n", &mut buf, 16).unwrap(), Some(b'\t'));
+    /// assert_eq!(reader.read_until_any(b" \t\n", &mut buf, 16).unwrap(), None);
+    /// assert_eq!(buf, b"foo bar\tbaz");
+    /// # assert!((&b"foo bar"[..]).read_until_any(b" ", &mut buf, 3).is_err());
+    /// # assert!((&b"foo bar"[..]).read_until_any(b" ", &mut buf, 4).is_ok());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_until_any(&mut self, delims: &[u8], buf: &mut alloc::vec::Vec<u8>, max: usize) -> Result<Option<u8>, ReadUntilError<Self::ReadError>> {
+        let mut total = 0;
+        loop {
+            let read = self.fill_buf().map_err(ReadUntilError::ReadingFailed)?;
+            if read.is_empty() {
+                break Ok(None);
+            }
+            if total == max {
+                break Err(LimitExceeded::new(max as u64).into());
+            }
+            let read = &read[..read.len().min(max - total)];
+            match read.iter().position(|byte| delims.contains(byte)) {
+                Some(pos) => {
+                    let delim = read[pos];
+                    buf.extend_from_slice(&read[..=pos]);
+                    self.consume(pos + 1);
+                    break Ok(Some(delim));
+                },
+                None => {
+                    buf.extend_from_slice(read);
+                    let len = read.len();
+                    total += len;
+                    self.consume(len);
+                },
+            }
+        }
+    }
+
+    /// Reads the bytes that are currently available, placing them into `buf`.
+    ///
+    /// Unlike [`read_to_end`](Self::read_to_end), this doesn't claim to have reached the end of
+    /// the stream - it only moves the data that is readily available in the buffer(s) of the
+    /// reader. Returning `Ok(0)` means there's no data available at the moment which may or may
+    /// not be the end of the stream.
+    ///
+    /// The default implementation calls [`fill_buf`](Self::fill_buf) once and then keeps
+    /// appending the contents of [`buffer`](Self::buffer) until it's empty. Thus readers having
+    /// their data split in multiple buffers (e.g. [`VecDeque`](alloc::collections::VecDeque)) are
+    /// drained completely without performing more I/O.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `fill_buf` fails. Nothing is appended to `buf` in that case.
+    #[cfg(feature = "alloc")]
+    fn drain_available(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
+        let read = self.fill_buf()?;
+        buf.extend_from_slice(read);
+        let mut total = read.len();
+        self.consume(total);
+        loop {
+            let read = self.buffer();
+            if read.is_empty() {
+                break Ok(total);
+            }
             buf.extend_from_slice(read);
             let len = read.len();
             total += len;
@@ -237,23 +795,175 @@ pub trait BufRead {
         Chain::new(self, other)
     }
 
-    /// Returns an adapter converting read and write errors using the closure `f`.
-    fn map_err<E, F>(self, f: F) -> MapErr<Self, F> where Self: BufWrite<WriteError=<Self as BufRead>::ReadError> + Sized, F: FnMut(Self::ReadError) -> E {
-        MapErr::new(self, f)
+    /// Returns an adapter tracking the size of the buffer so that consuming can be checked.
+    ///
+    /// The returned adapter provides [`try_consume`](CheckedConsume::try_consume) method which
+    /// returns an error instead of misbehaving if the amount is larger than the available buffer.
+    /// This is useful in defensive code such as fuzzing harnesses.
+    fn checked_consume(self) -> CheckedConsume<Self> where Self: Sized {
+        CheckedConsume::new(self)
     }
 
-    /// Returns an adapter converting read errors using the closure `f`.
-    fn map_read_err<E, F>(self, f: F) -> MapReadErr<Self, F> where Self: Sized, F: FnMut(Self::ReadError) -> E {
-        MapReadErr::new(self, f)
+    /// Returns an adapter counting the number of lines (`\n` bytes) consumed.
+    ///
+    /// This is useful for reporting line numbers in error messages of text parsers.
+    fn count_lines(self) -> LineCountReader<Self> where Self: Sized {
+        LineCountReader::new(self)
     }
 
-    /// Returns an adapter converting read and write errors using their [`Into::into`]
-    /// implementation.
-    fn unify_err<E>(self) -> UnifyErr<Self, E> where Self: BufWrite + Sized, Self::ReadError: Into<E>, Self::WriteError: Into<E> {
-        UnifyErr::new(self)
+    /// Returns an adapter converting Windows line endings (`\r\n`) to `\n`.
+    ///
+    /// The treatment of `\r` not followed by `\n` is controlled by `lone_cr`.
+    fn normalize_newlines(self, lone_cr: LoneCr) -> NormalizeNewlinesReader<Self> where Self: Sized {
+        NormalizeNewlinesReader::new(self, lone_cr)
     }
 
-    /// Returns an adapter providing implementations of [`std::io::Read`], [`std::io::BufRead`],
+    /// Returns an adapter XORing the read bytes with repeated `key`.
+    ///
+    /// This decodes data written using [`BufWrite::xor_write`] with the same key. Note that this
+    /// is a simple obfuscation, **not** encryption. Empty key leaves the data intact.
+    fn xor(self, key: &[u8]) -> XorReader<'_, Self> where Self: Sized {
+        XorReader::new(self, key)
+    }
+
+    /// Returns an adapter decoding run-length encoded data.
+    ///
+    /// The data is expected to be a sequence of (count, byte) pairs as produced by
+    /// [`BufWrite::rle_encode`].
+    fn rle_decode(self) -> RleReader<Self> where Self: Sized {
+        RleReader::new(self)
+    }
+
+    /// Returns an adapter reading frames prefixed with their length.
+    ///
+    /// The lengths are expected to be encoded as unsigned LEB128 varints as produced by
+    /// [`BufWrite::length_prefixed_write`]. They are stripped from the output.
+    fn length_prefixed(self) -> LengthPrefixReader<Self> where Self: Sized {
+        LengthPrefixReader::new(self)
+    }
+
+    /// Returns an adapter reading frames tagged with one-byte channel ids.
+    ///
+    /// Each frame consists of the channel id, the payload length encoded as unsigned LEB128
+    /// varint and the payload itself. Frames longer than `max_frame_len` are rejected before
+    /// allocating. Use [`Demux::next_frame`] to read the frames and route them by channel.
+    #[cfg(feature = "alloc")]
+    fn demux(self, max_frame_len: usize) -> Demux<Self> where Self: Sized {
+        Demux::new(self, max_frame_len)
+    }
+
+    /// Returns an adapter validating that the consumed bytes are UTF-8.
+    ///
+    /// The bytes are validated incrementally as they are consumed, characters split across
+    /// buffers are handled. Unlike reading into a `String` this still provides raw bytes and
+    /// doesn't need to buffer the whole stream. Invalid bytes are reported by the next call to
+    /// `fill_buf`.
+    fn validate_utf8(self) -> Utf8Validator<Self> where Self: Sized {
+        Utf8Validator::new(self)
+    }
+
+    /// Returns an adapter keeping at most `keep` bytes of each run of identical bytes.
+    ///
+    /// With `keep` set to 1 consecutive duplicates are collapsed to a single byte. This is
+    /// useful for normalizing noisy streams before parsing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keep` is 0.
+    fn dedup(self, keep: usize) -> DedupReader<Self> where Self: Sized {
+        DedupReader::new(self, keep)
+    }
+
+    /// Returns an adapter un-escaping the contents of JSON string.
+    ///
+    /// The reader should be positioned right after the opening quote. All escape sequences
+    /// including surrogate pairs are decoded and the adapter stops at the closing quote, leaving
+    /// it in this reader.
+    fn json_string_unescape(self) -> JsonStringUnescapeReader<Self> where Self: Sized {
+        JsonStringUnescapeReader::new(self)
+    }
+
+    /// Returns an adapter logging the consumed bytes in hexdump format.
+    ///
+    /// Each line of the output is formatted like `xxd` does it - offset, 16 bytes in hex and the
+    /// ASCII representation - and passed to `f`. The incomplete line is logged at the end of
+    /// the stream. The lines are not allocated so this works in `no_std` too.
+    fn hexdump<F: FnMut(core::fmt::Arguments<'_>)>(self, f: F) -> HexdumpReader<Self, F> where Self: Sized {
+        HexdumpReader::new(self, f)
+    }
+
+    /// Returns an adapter writing all consumed bytes to `writer`.
+    ///
+    /// This is useful for observing everything a decoder reads, e.g. for logging or debugging
+    /// protocols. Write errors are reported by the next call to `fill_buf`, see [`Tee`] for
+    /// details.
+    fn tee<W: BufWrite>(self, writer: W) -> Tee<Self, W> where Self: Sized {
+        Tee::new(self, writer)
+    }
+
+    /// Returns an adapter calling `f` with the bytes as they are consumed.
+    ///
+    /// This is similar to [`Iterator::inspect`] and useful for feeding a hasher or counting bytes
+    /// without a writer. The closure is only called from `consume`, so bytes returned from
+    /// `fill_buf` are observed once they are actually consumed and each byte is observed exactly
+    /// once.
+    fn inspect<F: FnMut(&[u8])>(self, f: F) -> Inspect<Self, F> where Self: Sized {
+        Inspect::new(self, f)
+    }
+
+    /// Returns an adapter tracking the number of consumed bytes.
+    ///
+    /// This is useful for reporting offsets in parse errors, especially for readers such as
+    /// `&[u8]` that don't remember where they started. The position is also returned from
+    /// [`stream_position_hint`](Self::stream_position_hint).
+    fn with_position(self) -> PositionReader<Self> where Self: Sized {
+        PositionReader::new(self)
+    }
+
+    /// Returns an iterator over the bytes of this reader.
+    ///
+    /// Each item is read using [`read_byte`](Self::read_byte), reading errors are yielded as
+    /// `Some(Err(error))`. This is the same as [`std::io::Read::bytes`] but since the reader is
+    /// buffered it's efficient.
+    fn bytes(self) -> Bytes<Self> where Self: Sized {
+        Bytes::new(self)
+    }
+
+    /// Returns an iterator decoding items using the closure `f`.
+    ///
+    /// Each call to `next` calls `f` with the reader. `Ok(Some(item))` is yielded as an item,
+    /// `Ok(None)` signals the (clean) end of the sequence and errors are yielded as
+    /// `Some(Err(error))`. The iterator returns `None` after the end or an error.
+    fn decode_iter<T, E, F>(self, f: F) -> DecodeIter<Self, F> where Self: Sized, F: FnMut(&mut Self) -> Result<Option<T>, E> {
+        DecodeIter::new(self, f)
+    }
+
+    /// Returns an adapter converting read and write errors using the closure `f`.
+    fn map_err<E, F>(self, f: F) -> MapErr<Self, F> where Self: BufWrite<WriteError=<Self as BufRead>::ReadError> + Sized, F: FnMut(Self::ReadError) -> E {
+        MapErr::new(self, f)
+    }
+
+    /// Returns an adapter converting read errors using the closure `f`.
+    fn map_read_err<E, F>(self, f: F) -> MapReadErr<Self, F> where Self: Sized, F: FnMut(Self::ReadError) -> E {
+        MapReadErr::new(self, f)
+    }
+
+    /// Returns an adapter converting read and write errors using their [`Into::into`]
+    /// implementation.
+    fn unify_err<E>(self) -> UnifyErr<Self, E> where Self: BufWrite + Sized, Self::ReadError: Into<E>, Self::WriteError: Into<E> {
+        UnifyErr::new(self)
+    }
+
+    /// Returns an adapter verifying CRC32 (IEEE) trailer at the end of the stream.
+    ///
+    /// The adapter returns all bytes except the last four which are interpreted as CRC32 of the
+    /// preceding bytes encoded using `endianness`. Reaching the end returns an error if the
+    /// checksum doesn't match or if the stream is too short to contain it.
+    fn verify_crc32(self, endianness: Endianness) -> Crc32Reader<Self> where Self: Sized {
+        Crc32Reader::new(self, endianness)
+    }
+
+    /// Returns an adapter providing implementations of [`std::io::Read`], [`std::io::BufRead`],
     /// and [`std::io::Write`].
     #[cfg(feature = "std")]
     fn into_std(self) -> AsStd<Self> where Self: BufWrite + Sized, Self::ReadError: Into<std::io::Error>, Self::WriteError: Into<std::io::Error> {
@@ -269,11 +979,34 @@ pub trait BufRead {
     /// Creates a "by reference" adapter for this instance of `BufRead`.
     ///
     /// The returned adapter also implements `BufRead` and will simply borrow this current writer.
-    fn by_ref(&mut self) -> &mut Self {
+    fn by_ref(&mut self) -> &mut Self where Self: Sized {
         self
     }
 }
 
+/// Implementation of [`BufRead::read_exact_uninit`] that can not be overridden.
+///
+/// Unsafe code relies on this initializing all bytes of `buf` when it returns `Ok`.
+fn read_exact_uninit<'a, R: BufRead + ?Sized>(reader: &mut R, buf: &'a mut [core::mem::MaybeUninit<u8>]) -> Result<&'a mut [u8], ReadExactError<R::ReadError>> {
+    let required = buf.len();
+    let mut filled = 0;
+    while filled < required {
+        let read = reader.fill_buf().map_err(ReadExactError::ReadingFailed)?;
+        if read.is_empty() {
+            return Err(ReadExactError::unexpected_end(required, filled));
+        }
+        let to_copy = (required - filled).min(read.len());
+        for (target, source) in buf[filled..(filled + to_copy)].iter_mut().zip(read) {
+            *target = core::mem::MaybeUninit::new(*source);
+        }
+        reader.consume(to_copy);
+        filled += to_copy;
+    }
+    // SAFETY: all bytes of the buffer were initialized by the loop above and MaybeUninit<u8>
+    // has the same layout as u8
+    Ok(unsafe { &mut *(buf as *mut [core::mem::MaybeUninit<u8>] as *mut [u8]) })
+}
+
 /// A trait for objects which are buffered, byte-oriented sinks.
 ///
 /// Implementors of the `BufWrite` trait are sometimes called 'writers'.
@@ -291,9 +1024,15 @@ pub trait BufRead {
 /// buffer. It's perfectly OK to not have it if writing doesn't involve a context switch or similar
 /// expensive operation. In other words, if performance of the writing to the writer is roughly
 /// the same when bytes are fed in individually or in large chunks then the writer may implement
-/// `BufRead`. If not it should provide some mechanism to add a buffer so it becomes less expensive
+/// `BufWrite`. If not it should provide some mechanism to add a buffer so it becomes less expensive
 /// to write byte-by-byte.
 ///
+/// Encoders are allowed to rely on this and write very small chunks (even single bytes). Thus
+/// unbuffered `std` writers such as [`std::fs::File`] or [`std::net::TcpStream`] intentionally
+/// don't implement `BufWrite` - writing to them directly would make a system call for every tiny
+/// chunk. Wrap them in [`std::io::BufWriter`] or use [`from_std_writer`], both of which buffer
+/// the writes.
+///
 /// [`write_all`]: BufWrite::write_all
 /// [`flush`]: BufWrite::flush
 pub trait BufWrite {
@@ -308,6 +1047,76 @@ pub trait BufWrite {
     /// error occurs. The first error that is generated from this method will be returned.
     fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError>;
 
+    /// Attempts to write an entire buffer into this writer, reporting progress on failure.
+    ///
+    /// This behaves the same as [`write_all`](Self::write_all) but the returned error also
+    /// contains the number of bytes that were written before the failure. This is useful for
+    /// resuming large writes.
+    ///
+    /// The reported number is a lower bound: writers that can not track the progress (the
+    /// default implementation) report `0`. The `std` writers report the exact number of bytes
+    /// accepted by the underlying [`std::io::Write::write`] calls. Fixed-size in-memory writers
+    /// such as `&mut [u8]` write as many bytes as fit before failing, unlike `write_all` which
+    /// doesn't write anything if the bytes don't fit.
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        self.write_all(bytes).map_err(|error| WriteAllError::new(0, error))
+    }
+
+    /// Writes as many bytes as the writer can accept, returning their number.
+    ///
+    /// Fixed-size writers such as `&mut [u8]` write as many bytes as fit and return `Ok(0)` once
+    /// they are full; growable writers accept all bytes. This makes it possible to spread a
+    /// single payload across multiple fixed buffers which `write_all` can't express.
+    ///
+    /// The default implementation calls [`write_all`](Self::write_all) with all bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lgio::BufWrite;
+    ///
+    /// let mut first = [0u8; 3];
+    /// let mut second = [0u8; 3];
+    /// let payload = [1, 2, 3, 4, 5];
+    /// let written = (&mut first[..]).write_some(&payload).unwrap();
+    /// (&mut second[..]).write_some(&payload[written..]).unwrap();
+    /// assert_eq!(first, [1, 2, 3]);
+    /// assert_eq!(second, [4, 5, 0]);
+    /// ```
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        self.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Writes a single byte.
+    ///
+    /// This is handy for encoders writing tags or other single-byte values. The default
+    /// implementation calls [`write_all`](Self::write_all) with a one-byte slice, in-memory
+    /// writers override it to avoid the slice handling.
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        self.write_all(&[byte])
+    }
+
+    write_int_methods! {
+        u16: write_u16_le, write_u16_be;
+        u32: write_u32_le, write_u32_be;
+        u64: write_u64_le, write_u64_be;
+        i16: write_i16_le, write_i16_be;
+        i32: write_i32_le, write_i32_be;
+        i64: write_i64_le, write_i64_be;
+    }
+
+    /// Hints that at least `additional` more bytes are going to be written.
+    ///
+    /// Growable writers such as `Vec<u8>` use this to pre-allocate the space which avoids
+    /// repeated reallocations when writing many small chunks. Encoders that know the size up front
+    /// should call this before writing.
+    ///
+    /// The default implementation does nothing.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
     /// Flush this output stream, ensuring that all intermediately buffered
     /// contents reach their destination.
     ///
@@ -317,19 +1126,363 @@ pub trait BufWrite {
     /// I/O errors.
     fn flush(&mut self) -> Result<(), Self::WriteError>;
 
+    /// Indicates whether calling [`flush`](Self::flush) has any effect.
+    ///
+    /// In-memory writers that never buffer anything (e.g. `Vec<u8>`) return `false` so that
+    /// generic code flushing frequently can skip the call entirely:
+    /// `if writer.needs_flush() { writer.flush()?; }`. Since the returned value is a constant for
+    /// such writers the check is optimized out. Implementations returning `false` must have
+    /// `flush` that does nothing.
+    ///
+    /// The default implementation returns `true`.
+    fn needs_flush(&self) -> bool {
+        true
+    }
+
+    /// Returns a boxed writer with the concrete type erased.
+    ///
+    /// The errors are converted into [`std::io::Error`] so writers of different types can be
+    /// stored uniformly, e.g. for pluggable output backends.
+    #[cfg(feature = "std")]
+    fn erase<'a>(self) -> ErasedWrite<'a> where Self: Sized + 'a, Self::WriteError: Into<std::io::Error> {
+        ErasedWrite::new(self)
+    }
+
     /// Returns an adapter converting write errors using the closure `f`.
     fn map_write_err<E, F: FnMut(Self::WriteError) -> E>(self, f: F) -> MapWriteErr<Self, F> where Self: Sized {
         MapWriteErr::new(self, f)
     }
 
+    /// Returns an adapter computing CRC32 (IEEE) of all bytes written through it.
+    ///
+    /// The checksum can be appended to the output using
+    /// [`finish_with_trailer`](Crc32Writer::finish_with_trailer).
+    fn crc32(self) -> Crc32Writer<Self> where Self: Sized {
+        Crc32Writer::new(self)
+    }
+
+    /// Returns an adapter computing Adler-32 of all bytes written through it.
+    ///
+    /// This is the checksum used by zlib. It can be appended to the output using
+    /// [`finish_with_trailer`](Adler32Writer::finish_with_trailer).
+    fn adler32(self) -> Adler32Writer<Self> where Self: Sized {
+        Adler32Writer::new(self)
+    }
+
+    /// Creates an adapter which will accept at most `limit` bytes.
+    ///
+    /// Writes exceeding the limit fail without writing anything, so the underlying writer never
+    /// receives more than `limit` bytes. This is the writing counterpart of [`BufRead::take`].
+    /// It has a different name so that it doesn't clash with it on types implementing both
+    /// traits.
+    fn take_write(self, limit: u64) -> WriteTake<Self> where Self: Sized {
+        WriteTake::new(self, limit)
+    }
+
+    /// Returns an adapter writing the first `boundary` bytes to this writer and the rest to
+    /// `next`.
+    ///
+    /// This is useful for writing a fixed-size header to a different sink than the body. Writes
+    /// straddling the boundary are split across both writers, see [`WriteChain`] for details.
+    fn chain_at<B: BufWrite<WriteError=Self::WriteError>>(self, boundary: u64, next: B) -> WriteChain<Self, B> where Self: Sized {
+        WriteChain::new(self, boundary, next)
+    }
+
+    /// Returns an adapter writing all bytes to both this writer and `other`.
+    ///
+    /// This is useful for mirroring output, e.g. logging everything sent over a connection. If
+    /// writing into this writer fails `other` is not written to, see [`Broadcast`] for details.
+    fn broadcast<B: BufWrite<WriteError=Self::WriteError>>(self, other: B) -> Broadcast<Self, B> where Self: Sized {
+        Broadcast::new(self, other)
+    }
+
+    /// Returns an adapter counting the written bytes.
+    ///
+    /// Combined with [`sink`] this measures the serialized size of data without storing it.
+    fn count(self) -> CountWrite<Self> where Self: Sized {
+        CountWrite::new(self)
+    }
+
+    /// Returns an adapter flushing the writer whenever at least `threshold` bytes were written
+    /// since the last flush.
+    ///
+    /// This bounds the latency caused by buffering when streaming. Explicit calls to `flush` are
+    /// forwarded and reset the counter.
+    fn flush_every(self, threshold: usize) -> FlushEvery<Self> where Self: Sized {
+        FlushEvery::new(self, threshold)
+    }
+
+    /// Returns an adapter skipping flushes when nothing was written since the last flush.
+    ///
+    /// This avoids redundant system calls caused by encoders that flush too often. It assumes
+    /// that flushing this writer is idempotent.
+    fn coalesce_flush(self) -> CoalesceFlush<Self> where Self: Sized {
+        CoalesceFlush::new(self)
+    }
+
+    /// Returns an adapter writing whole records terminated by `delimiter` at once.
+    ///
+    /// Bytes are buffered until the delimiter is written, then the entire record including the
+    /// delimiter is passed to the underlying writer in a single `write_all` call. This is useful
+    /// for message-oriented sinks such as datagram sockets which must not receive partial
+    /// records. Explicit `flush` writes the incomplete record.
+    #[cfg(feature = "alloc")]
+    fn records(self, delimiter: u8) -> RecordWriter<Self> where Self: Sized {
+        RecordWriter::new(self, delimiter)
+    }
+
+    /// Returns an adapter forwarding writes to this writer in chunks of at least `min_chunk`
+    /// bytes.
+    ///
+    /// Small writes are buffered until enough bytes accumulate while writes of at least
+    /// `min_chunk` bytes are passed through directly if nothing is buffered. This reduces the
+    /// number of system calls without imposing a fixed buffer capacity. Explicit `flush` writes
+    /// the buffered bytes regardless of their amount.
+    #[cfg(feature = "alloc")]
+    fn coalesce(self, min_chunk: usize) -> Coalesce<Self> where Self: Sized {
+        Coalesce::new(self, min_chunk)
+    }
+
+    /// Returns an adapter buffering all writes until they are committed.
+    ///
+    /// This allows encoding a whole message and discarding it if encoding fails midway instead
+    /// of emitting a partial message. The bytes are only written by
+    /// [`commit`](TransactionalWriter::commit), dropping the adapter discards them. Note that
+    /// the whole message is kept in memory.
+    #[cfg(feature = "alloc")]
+    fn transaction(self) -> TransactionalWriter<Self> where Self: Sized {
+        TransactionalWriter::new(self)
+    }
+
+    /// Returns an adapter escaping written bytes the same way [`core::ascii::escape_default`]
+    /// does.
+    ///
+    /// Printable ASCII characters are written verbatim, other bytes are written as `\t`, `\n`,
+    /// `\xNN` etc. This makes it possible to safely write arbitrary bytes into text logs.
+    fn escape_ascii(self) -> EscapeAsciiWriter<Self> where Self: Sized {
+        EscapeAsciiWriter::new(self)
+    }
+
+    /// Returns an adapter escaping written bytes for use inside JSON strings.
+    ///
+    /// `"`, `\` and control characters are escaped as JSON requires, other bytes including
+    /// parts of multi-byte UTF-8 characters are written verbatim. The input is assumed to be
+    /// valid UTF-8. The surrounding quotes are not written.
+    fn json_string_escape(self) -> JsonStringEscapeWriter<Self> where Self: Sized {
+        JsonStringEscapeWriter::new(self)
+    }
+
+    /// Returns an adapter counting the number of lines (`\n` bytes) written.
+    ///
+    /// It has a different name than [`BufRead::count_lines`] so that it doesn't clash with it on
+    /// types implementing both traits.
+    fn count_lines_write(self) -> LineCountWriter<Self> where Self: Sized {
+        LineCountWriter::new(self)
+    }
+
+    /// Returns an adapter converting `\n` line endings to `\r\n`.
+    ///
+    /// This is useful for protocols requiring CRLF line endings, such as HTTP or SMTP.
+    fn crlf(self) -> CrlfWriter<Self> where Self: Sized {
+        CrlfWriter::new(self)
+    }
+
+    /// Returns an adapter XORing the written bytes with repeated `key`.
+    ///
+    /// Note that this is a simple obfuscation, **not** encryption. Empty key leaves the data
+    /// intact. It has a different name than [`BufRead::xor`] so that it doesn't clash with it on
+    /// types implementing both traits.
+    fn xor_write(self, key: &[u8]) -> XorWriter<'_, Self> where Self: Sized {
+        XorWriter::new(self, key)
+    }
+
+    /// Returns an adapter encoding written data using run-length encoding.
+    ///
+    /// Runs of identical bytes are written as (count, byte) pairs. Note that the last run is only
+    /// written when the adapter is flushed or [finished](RleWriter::finish).
+    fn rle_encode(self) -> RleWriter<Self> where Self: Sized {
+        RleWriter::new(self)
+    }
+
+    /// Returns an adapter prefixing each write with its length.
+    ///
+    /// Note that framing is per `write_all` call - each call produces exactly one frame so
+    /// logical messages should be written at once. The length is encoded as unsigned LEB128
+    /// varint. It has a different name than [`BufRead::length_prefixed`] so that it doesn't clash
+    /// with it on types implementing both traits.
+    fn length_prefixed_write(self) -> LengthPrefixWriter<Self> where Self: Sized {
+        LengthPrefixWriter::new(self)
+    }
+
+    /// Returns an adapter rejecting lines longer than `max` bytes.
+    ///
+    /// The length doesn't include the terminating `\n` and is tracked across `write_all` calls.
+    /// This is useful for line-based protocols limiting the line length and can be combined with
+    /// [`crlf`](Self::crlf) - put this adapter in front of it to not count the `\r`s.
+    fn max_line_len(self, max: usize) -> LineLengthLimit<Self> where Self: Sized {
+        LineLengthLimit::new(self, max)
+    }
+
+    /// Returns an adapter remembering the last `N` bytes successfully written.
+    ///
+    /// This is useful for diagnostics - when encoding fails the tail of the output can be
+    /// dumped to see where it went wrong. It doesn't allocate.
+    fn keep_tail<const N: usize>(self) -> TailWriter<Self, N> where Self: Sized {
+        TailWriter::new(self)
+    }
+
+    /// Returns an adapter padding the output with `fill` bytes up to `total` length.
+    ///
+    /// The padding is written by [`finish`](PadToWriter::finish). Writes exceeding `total`
+    /// bytes fail without writing anything. This is useful for fixed-length records.
+    fn pad_to(self, total: u64, fill: u8) -> PadToWriter<Self> where Self: Sized {
+        PadToWriter::new(self, total, fill)
+    }
+
+    /// Returns an adapter writing placeholders that can be filled in later.
+    ///
+    /// This is the usual way to write a length prefix before a body of unknown length: reserve
+    /// the field using [`BackfillWriter::reserve_u32`], write the body and then call
+    /// [`BackfillWriter::fill`]. The write position is restored after filling.
+    fn backfill(self) -> BackfillWriter<Self> where Self: Seek + Sized {
+        BackfillWriter::new(self)
+    }
+
+    /// Returns an adapter checking that exactly `expected` bytes are written.
+    ///
+    /// Writes exceeding `expected` bytes fail without writing anything, missing bytes are
+    /// reported by [`finish`](ExactSizeWriter::finish). This catches bugs in size calculations
+    /// of serializers that compute the length up front.
+    fn expect_exact(self, expected: u64) -> ExactSizeWriter<Self> where Self: Sized {
+        ExactSizeWriter::new(self, expected)
+    }
+
+    /// Returns an adapter calling `f` with the number of bytes before each write.
+    ///
+    /// The adapter doesn't do any timing itself. The callback can implement throttling or
+    /// backpressure, e.g. by waiting on a hardware timer, without this crate depending on any
+    /// clock.
+    fn pace<F: FnMut(usize)>(self, f: F) -> PacedWriter<Self, F> where Self: Sized {
+        PacedWriter::new(self, f)
+    }
+
     /// Creates a "by reference" adapter for this instance of `BufWrite`.
     ///
     /// The returned adapter also implements `BufWrite` and will simply borrow this current writer.
-    fn by_ref(&mut self) -> &mut Self {
+    fn by_ref(&mut self) -> &mut Self where Self: Sized {
         self
     }
 }
 
+/// A source of bytes that is not buffered.
+///
+/// This is the minimal interface needed by [`BufReader`] to provide [`BufRead`] on top of
+/// sources that can only produce bytes into a provided buffer, e.g. peripherals of embedded
+/// devices. It's intentionally not a general-purpose reading trait, use [`BufRead`] instead.
+pub trait RawRead {
+    /// The error returned when reading fails.
+    type ReadError;
+
+    /// Reads some bytes into `buf` and returns their number.
+    ///
+    /// Returning `Ok(0)` for non-empty `buf` indicates the end of the stream. Implementors
+    /// should retry interrupted reads.
+    fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize, Self::ReadError>;
+}
+
+/// A sink of bytes that is not buffered.
+///
+/// This is the minimal interface needed by [`BufWriter`] to provide [`BufWrite`] on top of
+/// sinks for which each call is expensive, e.g. flash memory. It's intentionally not a
+/// general-purpose writing trait, use [`BufWrite`] instead.
+pub trait RawWrite {
+    /// The error returned when writing fails.
+    type WriteError;
+
+    /// Writes all `bytes` into the sink.
+    fn write_raw(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError>;
+}
+
+/// A trait for objects that have a cursor which can be moved within a stream of bytes.
+///
+/// This is similar to [`std::io::Seek`] but the error type is associated. Combined with
+/// [`BufWrite`] it allows patching already-written data, e.g. filling in a length field after
+/// the body was written.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use lgio::{BufWrite, Seek, SeekFrom};
+///
+/// let mut writer = std::io::Cursor::new([0u8; 8]);
+/// writer.write_all(&[0; 4]).unwrap();
+/// writer.write_all(b"body").unwrap();
+/// let end = writer.stream_position().unwrap();
+/// writer.seek(SeekFrom::Start(0)).unwrap();
+/// writer.write_all(&(end as u32 - 4).to_le_bytes()).unwrap();
+/// assert_eq!(&writer.into_inner(), b"\x04\x00\x00\x00body");
+/// # }
+/// ```
+pub trait Seek {
+    /// The error returned when seeking fails.
+    type SeekError;
+
+    /// Seeks to an offset, in bytes, in a stream.
+    ///
+    /// If successful, this returns the new position from the start of the stream.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::SeekError>;
+
+    /// Returns the current seek position from the start of the stream.
+    fn stream_position(&mut self) -> Result<u64, Self::SeekError> {
+        self.seek(SeekFrom::Current(0))
+    }
+}
+
+/// Enumeration of possible methods to seek within a stream.
+///
+/// This is the same as [`std::io::SeekFrom`] but available in `no_std`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SeekFrom {
+    /// Sets the offset to the provided number of bytes.
+    Start(u64),
+    /// Sets the offset to the size of the stream plus the specified number of bytes.
+    End(i64),
+    /// Sets the offset to the current position plus the specified number of bytes.
+    Current(i64),
+}
+
+#[cfg(feature = "std")]
+impl From<SeekFrom> for std::io::SeekFrom {
+    fn from(pos: SeekFrom) -> Self {
+        match pos {
+            SeekFrom::Start(offset) => std::io::SeekFrom::Start(offset),
+            SeekFrom::End(offset) => std::io::SeekFrom::End(offset),
+            SeekFrom::Current(offset) => std::io::SeekFrom::Current(offset),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::SeekFrom> for SeekFrom {
+    fn from(pos: std::io::SeekFrom) -> Self {
+        match pos {
+            std::io::SeekFrom::Start(offset) => SeekFrom::Start(offset),
+            std::io::SeekFrom::End(offset) => SeekFrom::End(offset),
+            std::io::SeekFrom::Current(offset) => SeekFrom::Current(offset),
+        }
+    }
+}
+
+/// Byte order of multi-byte values.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Endianness {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
 /// Returns a reader that has no data (is at end).
 pub fn empty() -> Empty {
     Empty
@@ -361,13 +1514,658 @@ pub struct Sink;
 #[non_exhaustive]
 pub struct Null;
 
+/// Returns a reader providing UTF-8 bytes of the string.
+///
+/// `&str` itself can not implement [`BufRead`] because consuming a part of a multi-byte character
+/// would break its invariants so a dedicated reader tracking the position is returned instead.
+///
+/// # Example
+///
+/// ```
+/// use lgio::BufRead;
+///
+/// let mut buf = [0u8; 5];
+/// lgio::read_str("hello").read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"hello");
+/// ```
+pub fn read_str(string: &str) -> StrReader<'_> {
+    StrReader::new(string)
+}
+
+/// A reader returning bytes of a string.
+///
+/// This is a [`TrackedSliceReader`] that also remembers the string.
+#[derive(Debug, Clone)]
+pub struct StrReader<'a> {
+    string: &'a str,
+    reader: TrackedSliceReader<'a>,
+}
+
+impl<'a> StrReader<'a> {
+    /// Creates the reader starting at the beginning of `string`.
+    pub fn new(string: &'a str) -> Self {
+        StrReader {
+            string,
+            reader: TrackedSliceReader::new(string.as_bytes()),
+        }
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn position(&self) -> usize {
+        self.reader.consumed()
+    }
+
+    /// Returns the bytes that were not consumed yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.reader.remaining()
+    }
+
+    /// Returns the whole underlying string.
+    pub fn get_ref(&self) -> &'a str {
+        self.string
+    }
+}
+
+impl<'a> From<&'a str> for StrReader<'a> {
+    fn from(string: &'a str) -> Self {
+        StrReader::new(string)
+    }
+}
+
+/// Returns a reader providing bytes from a tuple of readers one after another.
+///
+/// This is a compile-time-fixed alternative to nesting [`BufRead::chain`], supporting tuples
+/// of two to six readers sharing the error type.
+///
+/// # Example
+///
+/// ```
+/// use lgio::BufRead;
+///
+/// let mut reader = lgio::chain_tuple((&b"head"[..], &b"-"[..], &b"tail"[..]));
+/// let mut buf = [0u8; 9];
+/// reader.read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"head-tail");
+/// ```
+pub fn chain_tuple<T>(readers: T) -> TupleChain<T> where TupleChain<T>: BufRead {
+    TupleChain::new(readers)
+}
+
+/// Returns a reader providing bytes from a sequence of slices.
+///
+/// This allows reading discontiguous data without allocating or copying it. The slices are
+/// returned from `fill_buf` one by one.
+pub fn from_slices<'a, I: IntoIterator<Item=&'a [u8]>>(slices: I) -> SliceIterReader<'a, I::IntoIter> {
+    SliceIterReader::new(slices.into_iter())
+}
+
+/// Byte-sized types that can be reinterpreted as `u8`.
+///
+/// This trait is sealed and implemented only for types that are exactly one byte with alignment
+/// of one and no invalid bit patterns, so reading them as `u8` is sound.
+pub trait BytePlain: byte_plain::Sealed {}
+
+mod byte_plain {
+    use core::num::Wrapping;
+
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for i8 {}
+    impl Sealed for Wrapping<u8> {}
+    impl Sealed for Wrapping<i8> {}
+}
+
+impl BytePlain for u8 {}
+impl BytePlain for i8 {}
+impl BytePlain for core::num::Wrapping<u8> {}
+impl BytePlain for core::num::Wrapping<i8> {}
+
+/// Returns a reader providing the bytes of a slice of byte-sized values.
+///
+/// This avoids manual casts when a decoder receives bytes stored as e.g. `[Wrapping<u8>]`.
+///
+/// # Example
+///
+/// ```
+/// use core::num::Wrapping;
+/// use lgio::BufRead;
+///
+/// let mut reader = lgio::as_byte_reader(&[Wrapping(1u8), Wrapping(2)]);
+/// assert_eq!(reader.fill_buf().unwrap_or_else(|error| match error {}), [1, 2]);
+/// ```
+pub fn as_byte_reader<T: BytePlain>(slice: &[T]) -> impl BufRead<ReadError = core::convert::Infallible> + '_ {
+    // SAFETY: BytePlain is sealed and only implemented for types with the same size, alignment
+    // and validity as u8
+    unsafe { core::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), slice.len()) }
+}
+
+/// Returns a writer writing the same bytes into all `writers`.
+///
+/// This is useful for fan-out logging or producing multiple copies of serialized data in a
+/// single pass. Errors carry the index of the writer that failed.
+pub fn multiplex<W: BufWrite>(writers: &mut [W]) -> Multiplex<'_, W> {
+    Multiplex::new(writers)
+}
+
+/// Copies all bytes from `reader` to `writer` until the reader ends.
+///
+/// The bytes are written directly from the buffer returned by [`fill_buf`](BufRead::fill_buf)
+/// so no intermediate buffer is needed. Returns the total number of copied bytes.
+///
+/// # Errors
+///
+/// Returns the error from reading or writing. The bytes copied before the error stay written and
+/// consumed.
+///
+/// # Example
+///
+/// ```
+/// let mut reader = &[1, 2, 3][..];
+/// let mut writer = [0u8; 4];
+/// let copied = lgio::copy(&mut reader, &mut &mut writer[..]).unwrap();
+/// assert_eq!(copied, 3);
+/// assert_eq!(writer, [1, 2, 3, 0]);
+/// ```
+pub fn copy<R: BufRead + ?Sized, W: BufWrite + ?Sized>(reader: &mut R, writer: &mut W) -> Result<u64, CopyError<R::ReadError, W::WriteError>> {
+    let mut total = 0;
+    loop {
+        let buf = reader.fill_buf().map_err(CopyError::ReadingFailed)?;
+        if buf.is_empty() {
+            break Ok(total);
+        }
+        let len = buf.len();
+        writer.write_all(buf).map_err(CopyError::WritingFailed)?;
+        reader.consume(len);
+        total += len as u64;
+    }
+}
+
 /// Returns an adapter for arbitrary [`std::io::BufRead`]er.
 ///
 /// This is only intended for types from external crates implementing `std::io::BufRead`.
 /// Types from `std` that implement `std::io::BufRead` already implement `BufRead`.
-///
-/// Note that write version is **not** provided beause there's no `std::io::BufWrite`.
 #[cfg(feature = "std")]
 pub fn from_std_reader<R: std::io::BufRead>(reader: R) -> StdBufRead<R> {
     StdBufRead::new(reader)
 }
+
+/// Returns an adapter for arbitrary [`std::io::Write`]r.
+///
+/// This is intended for types such as [`std::net::TcpStream`] that don't implement `BufWrite`.
+/// The adapter buffers small writes so encoders writing tiny chunks don't perform a system call
+/// for each of them. Call [`BufWrite::flush`] to write out the buffered bytes.
+#[cfg(feature = "std")]
+pub fn from_std_writer<W: std::io::Write>(writer: W) -> StdBufWrite<W> {
+    StdBufWrite::new(writer)
+}
+
+/// Opens a file for buffered reading.
+///
+/// This is a shorthand for `BufReader::new(File::open(path)?)`.
+///
+/// # Errors
+///
+/// Returns the error returned by [`std::fs::File::open`].
+#[cfg(feature = "std")]
+pub fn open_buffered<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<std::io::BufReader<std::fs::File>> {
+    std::fs::File::open(path).map(std::io::BufReader::new)
+}
+
+/// Creates (or truncates) a file for buffered writing.
+///
+/// This is a shorthand for `BufWriter::new(File::create(path)?)`. Note that
+/// [`std::io::BufWriter`] ignores errors when dropped so you should call
+/// [`flush`](BufWrite::flush) after writing.
+///
+/// # Errors
+///
+/// Returns the error returned by [`std::fs::File::create`].
+#[cfg(feature = "std")]
+pub fn create_buffered<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<std::io::BufWriter<std::fs::File>> {
+    std::fs::File::create(path).map(std::io::BufWriter::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufRead, BufWrite};
+
+    #[test]
+    fn read_exact_into_chunked() {
+        let chunks: [&[u8]; 3] = [&[1], &[2, 3], &[4]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        assert_eq!(reader.read_exact_into([0u8; 3]).unwrap(), [1, 2, 3]);
+        let error = reader.read_exact_into([0u8; 2]).unwrap_err().into_unexpected_end();
+        assert_eq!(error.available(), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_until_any_chunked() {
+        use crate::error::ReadUntilError;
+
+        let chunks: [&[u8]; 3] = [b"ab", b"c;d", b"e,f"];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = alloc::vec::Vec::new();
+        assert_eq!(reader.read_until_any(b",;", &mut buf, 8).unwrap_or_else(|error| panic!("{}", error)), Some(b';'));
+        assert_eq!(buf, b"abc;");
+        buf.clear();
+        assert_eq!(reader.read_until_any(b",;", &mut buf, 8).unwrap_or_else(|error| panic!("{}", error)), Some(b','));
+        assert_eq!(buf, b"de,");
+        buf.clear();
+        assert_eq!(reader.read_until_any(b",;", &mut buf, 8).unwrap_or_else(|error| panic!("{}", error)), None);
+        assert_eq!(buf, b"f");
+
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = alloc::vec::Vec::new();
+        match reader.read_until_any(b",;", &mut buf, 3) {
+            Err(ReadUntilError::LimitExceeded(error)) => assert_eq!(error.limit(), 3),
+            _ => panic!("the limit was not enforced"),
+        }
+        assert_eq!(buf, b"abc");
+    }
+
+    #[test]
+    fn read_exact_infallible_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [0; 4];
+        reader.read_exact_infallible(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4]);
+        let error = reader.read_exact_infallible(&mut buf).unwrap_err();
+        assert_eq!(error.total_required(), 4);
+        assert_eq!(error.available(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri doesn't support file system access")]
+    fn buffered_file_round_trip() {
+        let path = std::env::temp_dir().join(std::format!("lgio-buffered-{}", std::process::id()));
+        let mut writer = crate::create_buffered(&path).unwrap();
+        writer.write_all(b"hello").unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        let mut reader = crate::open_buffered(&path).unwrap();
+        let mut buf = std::vec::Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(buf, b"hello");
+
+        let error = crate::open_buffered(&path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn drain_available_wrapped_deque() {
+        let mut vec = alloc::vec::Vec::with_capacity(4);
+        vec.extend_from_slice(&[0, 0, 1, 2]);
+        let mut deque = alloc::collections::VecDeque::from(vec);
+        deque.pop_front();
+        deque.pop_front();
+        deque.extend([3, 4]);
+        assert!(!deque.as_slices().1.is_empty());
+
+        let mut buf = alloc::vec::Vec::new();
+        assert_eq!(deque.drain_available(&mut buf).unwrap_or_else(|infallible| match infallible {}), 4);
+        assert_eq!(buf, [1, 2, 3, 4]);
+        assert_eq!(deque.drain_available(&mut buf).unwrap_or_else(|infallible| match infallible {}), 0);
+        deque.push_back(5);
+        assert_eq!(deque.drain_available(&mut buf).unwrap_or_else(|infallible| match infallible {}), 1);
+        assert_eq!(buf, [1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn drain_available_chunked() {
+        let chunks: [&[u8]; 2] = [&[1, 2], &[3]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = alloc::vec::Vec::new();
+        assert_eq!(reader.drain_available(&mut buf).unwrap_or_else(|infallible| match infallible {}), 2);
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(reader.drain_available(&mut buf).unwrap_or_else(|infallible| match infallible {}), 1);
+        assert_eq!(buf, [1, 2, 3]);
+    }
+
+    #[test]
+    fn read_exact_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4]);
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [5, 6]);
+        assert!(reader.read_exact(&mut buf[..1]).is_err());
+    }
+
+    /// Reader returning one byte and tracking calls to `consume`.
+    struct OneByte {
+        consumed: bool,
+        consume_calls: usize,
+    }
+
+    impl BufRead for OneByte {
+        type ReadError = core::convert::Infallible;
+
+        fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+            Ok(if self.consumed { &[] } else { &[42] })
+        }
+
+        fn consume(&mut self, amount: usize) {
+            assert!(amount <= 1);
+            self.consume_calls += 1;
+            if amount == 1 {
+                self.consumed = true;
+            }
+        }
+    }
+
+    #[test]
+    fn read_byte_consume_contract() {
+        let mut reader = OneByte { consumed: false, consume_calls: 0 };
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(42));
+        assert_eq!(reader.consume_calls, 1);
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+        assert_eq!(reader.consume_calls, 1);
+    }
+
+    #[test]
+    fn read_exact_unexpected_end_counts() {
+        let chunks: [&[u8]; 2] = [&[1, 2], &[3]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [0; 5];
+        let error = reader.read_exact(&mut buf).unwrap_err().into_unexpected_end();
+        assert_eq!(error.total_required(), 5);
+        assert_eq!(error.available(), 3);
+    }
+
+    #[test]
+    fn write_byte_slice() {
+        let mut buf = [0u8; 2];
+        let mut writer = &mut buf[..];
+        writer.write_byte(1).unwrap();
+        writer.write_byte(2).unwrap();
+        assert!(writer.write_byte(3).is_err());
+        assert_eq!(buf, [1, 2]);
+
+        let mut buf = [0u8; 1];
+        let mut writer = crate::adapters::SliceWriter::new(&mut buf);
+        writer.write_byte(1).unwrap();
+        assert!(writer.write_byte(2).is_err());
+        assert_eq!(writer.written(), [1]);
+    }
+
+    #[test]
+    fn read_ints_chunked() {
+        let chunks: [&[u8]; 3] = [&[0x01, 0x02, 0x03], &[0x04, 0xFF], &[0xFE, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        assert_eq!(reader.read_u16_be().unwrap(), 0x0102);
+        assert_eq!(reader.read_u32_le().unwrap(), 0xFEFF_0403);
+        assert_eq!(reader.read_i64_le().unwrap(), i64::MIN);
+        assert_eq!(reader.read_u16_le().unwrap_err().into_unexpected_end().available(), 0);
+    }
+
+    #[test]
+    fn write_ints_round_trip() {
+        let mut buf = [0u8; 28];
+        let mut writer = &mut buf[..];
+        writer.write_u16_le(0x0102).unwrap();
+        writer.write_u16_be(0x0102).unwrap();
+        writer.write_u32_be(0xDEAD_BEEF).unwrap();
+        writer.write_i32_le(-2).unwrap();
+        writer.write_u64_le(u64::MAX - 1).unwrap();
+        writer.write_i64_be(i64::MIN).unwrap();
+        assert!(writer.is_empty());
+        assert_eq!(buf[..4], [0x02, 0x01, 0x01, 0x02]);
+
+        let mut reader = &buf[..];
+        assert_eq!(reader.read_u16_le().unwrap(), 0x0102);
+        assert_eq!(reader.read_u16_be().unwrap(), 0x0102);
+        assert_eq!(reader.read_u32_be().unwrap(), 0xDEAD_BEEF);
+        assert_eq!(reader.read_i32_le().unwrap(), -2);
+        assert_eq!(reader.read_u64_le().unwrap(), u64::MAX - 1);
+        assert_eq!(reader.read_i64_be().unwrap(), i64::MIN);
+    }
+
+    #[test]
+    fn read_into_slice_lengths() {
+        let chunks: [&[u8]; 2] = [&[1, 2], &[3, 4]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [0; 3];
+        assert_eq!(reader.read_into_slice(&mut buf).unwrap_or_else(|infallible| match infallible {}), 3);
+        assert_eq!(buf, [1, 2, 3]);
+        assert_eq!(reader.read_into_slice(&mut buf).unwrap_or_else(|infallible| match infallible {}), 1);
+        assert_eq!(buf[0], 4);
+        assert_eq!(reader.read_into_slice(&mut buf).unwrap_or_else(|infallible| match infallible {}), 0);
+
+        let mut buf = [0; 4];
+        assert_eq!((&[1, 2, 3, 4][..]).read_into_slice(&mut buf).unwrap_or_else(|infallible| match infallible {}), 4);
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn skip_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        reader.skip(4).unwrap();
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(5));
+        let error = reader.skip(3).unwrap_err().into_unexpected_end();
+        assert_eq!(error.total_required(), 3);
+        assert_eq!(error.available(), 1);
+        reader.skip(0).unwrap();
+    }
+
+    #[test]
+    fn peek_byte() {
+        let chunks: [&[u8]; 2] = [&[1], &[2]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        assert_eq!(reader.peek_byte().unwrap_or_else(|infallible| match infallible {}), Some(1));
+        assert_eq!(reader.peek_byte().unwrap_or_else(|infallible| match infallible {}), Some(1));
+        reader.consume(1);
+        assert_eq!(reader.peek_byte().unwrap_or_else(|infallible| match infallible {}), Some(2));
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(2));
+        assert_eq!(reader.peek_byte().unwrap_or_else(|infallible| match infallible {}), None);
+
+        let mut reader = &[3, 4][..];
+        assert_eq!(reader.peek_byte().unwrap_or_else(|infallible| match infallible {}), Some(3));
+        assert_eq!(reader.peek_byte().unwrap_or_else(|infallible| match infallible {}), Some(3));
+        assert_eq!(reader, [3, 4]);
+    }
+
+    #[test]
+    fn byte_reader() {
+        let mut reader = crate::as_byte_reader(&[-1i8, 2]);
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(0xFF));
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(2));
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), None);
+    }
+
+    #[test]
+    fn read_exact_to_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [0; 4];
+        reader.read_exact_to(4, &mut &mut buf[..]).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4]);
+        let mut buf = [0; 4];
+        match reader.read_exact_to(4, &mut &mut buf[..]) {
+            Err(crate::error::CopyExactError::UnexpectedEnd(error)) => {
+                assert_eq!(error.total_required(), 4);
+                assert_eq!(error.available(), 2);
+            },
+            _ => panic!("expected unexpected end"),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_until_boundary() {
+        let chunks: [&[u8]; 3] = [&b"ab\n"[..], &b"cd"[..], &b"\nef"[..]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = alloc::vec::Vec::new();
+        assert_eq!(reader.read_until(b'\n', &mut buf).unwrap_or_else(|infallible| match infallible {}), 3);
+        assert_eq!(buf, b"ab\n");
+        buf.clear();
+        assert_eq!(reader.read_until(b'\n', &mut buf).unwrap_or_else(|infallible| match infallible {}), 3);
+        assert_eq!(buf, b"cd\n");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_until_eof() {
+        let mut reader = &b"abc"[..];
+        let mut buf = alloc::vec::Vec::new();
+        assert_eq!(reader.read_until(b'\n', &mut buf).unwrap_or_else(|infallible| match infallible {}), 3);
+        assert_eq!(buf, b"abc");
+        assert_eq!(reader.read_until(b'\n', &mut buf).unwrap_or_else(|infallible| match infallible {}), 0);
+        assert_eq!(crate::empty().read_until(b'\n', &mut buf).unwrap_or_else(|infallible| match infallible {}), 0);
+        assert_eq!(buf, b"abc");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_line_invalid_utf8() {
+        let mut reader = &b"ok\n\xff\nend"[..];
+        let mut line = alloc::string::String::new();
+        assert_eq!(reader.read_line(&mut line).unwrap_or_else(|error| panic!("{}", error)), 3);
+        assert!(matches!(reader.read_line(&mut line), Err(crate::error::ReadLineError::InvalidUtf8(_))));
+        assert_eq!(line, "ok\n");
+        assert_eq!(reader.read_line(&mut line).unwrap_or_else(|error| panic!("{}", error)), 3);
+        assert_eq!(line, "ok\nend");
+        assert_eq!(reader.read_line(&mut line).unwrap_or_else(|error| panic!("{}", error)), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_vec_exact_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        assert_eq!(reader.read_vec_exact(4).unwrap_or_else(|error| panic!("{}", error)), [1, 2, 3, 4]);
+        let error = reader.read_vec_exact(4).unwrap_err().into_unexpected_end();
+        assert_eq!(error.available(), 2);
+        assert!(reader.read_vec_exact(0).unwrap_or_else(|error| panic!("{}", error)).is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_exact_into_spare_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut vec = alloc::vec::Vec::new();
+        reader.read_exact_into_spare(&mut vec, 4).unwrap();
+        assert_eq!(vec, [1, 2, 3, 4]);
+        assert!(reader.read_exact_into_spare(&mut vec, 3).is_err());
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_exact_into_spare_ignores_overridden_uninit() {
+        struct Lying<'a>(&'a [u8]);
+
+        impl BufRead for Lying<'_> {
+            type ReadError = core::convert::Infallible;
+
+            fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+                self.0.fill_buf()
+            }
+
+            fn consume(&mut self, amount: usize) {
+                self.0.consume(amount)
+            }
+
+            fn buffer(&self) -> &[u8] {
+                self.0
+            }
+
+            fn read_exact_uninit<'a>(&mut self, _buf: &'a mut [core::mem::MaybeUninit<u8>]) -> Result<&'a mut [u8], crate::error::ReadExactError<Self::ReadError>> {
+                Ok(&mut [])
+            }
+        }
+
+        let mut reader = Lying(&[1, 2, 3]);
+        assert_eq!(reader.read_vec_exact(2).unwrap(), [1, 2]);
+        assert_eq!(reader.0, [3]);
+    }
+
+    #[cfg(all(feature = "alloc", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "did not advance")]
+    fn read_to_end_detects_stuck_consume() {
+        static DATA: [u8; 1] = [42];
+
+        struct Stuck;
+
+        impl BufRead for Stuck {
+            type ReadError = core::convert::Infallible;
+
+            fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+                Ok(&DATA)
+            }
+
+            fn consume(&mut self, _amount: usize) {}
+
+            fn buffer(&self) -> &[u8] {
+                &DATA
+            }
+        }
+
+        let _ = Stuck.read_to_end(&mut alloc::vec::Vec::new());
+    }
+
+    #[test]
+    fn read_exact_uninit_chunked() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [core::mem::MaybeUninit::uninit(); 4];
+        assert_eq!(reader.read_exact_uninit(&mut buf).unwrap(), [1, 2, 3, 4]);
+        assert!(reader.read_exact_uninit(&mut buf[..3]).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn copy_to_vec() {
+        let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5, 6]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut writer = alloc::vec::Vec::new();
+        let copied = crate::copy(&mut reader, &mut writer).unwrap_or_else(|error| match error {
+            crate::error::CopyError::ReadingFailed(infallible) | crate::error::CopyError::WritingFailed(infallible) => match infallible {},
+        });
+        assert_eq!(copied, 6);
+        assert_eq!(writer, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn copy_write_error() {
+        let chunks: [&[u8]; 2] = [&[1, 2], &[3, 4]];
+        let mut reader = crate::from_slices(chunks.iter().copied());
+        let mut buf = [0u8; 3];
+        let mut writer = &mut buf[..];
+        match crate::copy(&mut reader, &mut writer) {
+            Err(crate::error::CopyError::WritingFailed(_)) => (),
+            _ => panic!("expected writing to fail"),
+        }
+        let mut rest = [0u8; 2];
+        reader.read_exact(&mut rest).unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(rest, [3, 4]);
+        assert_eq!(buf, [1, 2, 0]);
+    }
+
+    #[test]
+    fn read_str() {
+        let mut reader = crate::read_str("a€b");
+        assert_eq!(reader.size_hint(), (5, Some(5)));
+        assert_eq!(reader.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(b'a'));
+        reader.consume(2);
+        assert_eq!(reader.position(), 3);
+        assert_eq!(reader.stream_position_hint(), Some(3));
+        assert_eq!(reader.remaining(), [0xAC, b'b']);
+        assert_eq!(reader.buffer(), [0xAC, b'b']);
+        assert_eq!(reader.get_ref(), "a€b");
+    }
+}
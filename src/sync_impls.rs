@@ -1,5 +1,7 @@
-use crate::{BufRead, BufWrite, Empty, Sink, Null};
-use crate::error::BufferOverflow;
+use crate::{BufRead, BufWrite, Seek, SeekFrom, RawRead, RawWrite, Empty, Sink, Null, StrReader};
+use crate::error::{BufferOverflow, WriteAllError};
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
 
 impl<T: BufRead + ?Sized> BufRead for &'_ mut T {
     type ReadError = T::ReadError;
@@ -11,8 +13,21 @@ impl<T: BufRead + ?Sized> BufRead for &'_ mut T {
     fn consume(&mut self, amount: usize) {
         (*self).consume(amount)
     }
+
+    fn buffer(&self) -> &[u8] {
+        (**self).buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        (**self).stream_position_hint()
+    }
 }
 
+#[allow(clippy::needless_lifetimes)]
 impl<'a> BufRead for &'a [u8] {
     type ReadError = core::convert::Infallible;
 
@@ -23,8 +38,17 @@ impl<'a> BufRead for &'a [u8] {
     fn consume(&mut self, amount: usize) {
         *self = &self[amount..];
     }
+
+    fn buffer(&self) -> &[u8] {
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
 }
 
+#[allow(clippy::needless_lifetimes, clippy::mem_replace_with_default)]
 impl<'a> BufRead for &'a mut [u8] {
     type ReadError = core::convert::Infallible;
 
@@ -36,6 +60,14 @@ impl<'a> BufRead for &'a mut [u8] {
         let this = core::mem::replace(self, &mut []);
         *self = &mut this[amount..];
     }
+
+    fn buffer(&self) -> &[u8] {
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
 }
 
 impl<T: BufWrite + ?Sized> BufWrite for &'_ mut T {
@@ -45,11 +77,32 @@ impl<T: BufWrite + ?Sized> BufWrite for &'_ mut T {
         (*self).write_all(bytes)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        (*self).write_all_counting(bytes)
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        (*self).write_some(bytes)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        (*self).write_byte(byte)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        (*self).reserve(additional)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         (*self).flush()
     }
+
+    fn needs_flush(&self) -> bool {
+        (**self).needs_flush()
+    }
 }
 
+#[allow(clippy::needless_lifetimes, clippy::mem_replace_with_default)]
 impl<'a> BufWrite for &'a mut [u8] {
     type WriteError = BufferOverflow;
 
@@ -65,9 +118,59 @@ impl<'a> BufWrite for &'a mut [u8] {
         Ok(())
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        write_all_counting_fixed(self, bytes)
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        let len = bytes.len().min(self.len());
+        self.write_all(&bytes[..len])?;
+        Ok(len)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        let this = core::mem::take(self);
+        match this.split_first_mut() {
+            Some((target, remaining)) => {
+                *target = byte;
+                *self = remaining;
+                Ok(())
+            },
+            None => Err(BufferOverflow::new(1)),
+        }
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         Ok(())
     }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Seek + ?Sized> Seek for &'_ mut T {
+    type SeekError = T::SeekError;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::SeekError> {
+        (*self).seek(pos)
+    }
+}
+
+impl<T: RawRead + ?Sized> RawRead for &'_ mut T {
+    type ReadError = T::ReadError;
+
+    fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize, Self::ReadError> {
+        (*self).read_raw(buf)
+    }
+}
+
+impl<T: RawWrite + ?Sized> RawWrite for &'_ mut T {
+    type WriteError = T::WriteError;
+
+    fn write_raw(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        (*self).write_raw(bytes)
+    }
 }
 
 impl BufRead for Empty {
@@ -80,6 +183,35 @@ impl BufRead for Empty {
     fn consume(&mut self, amount: usize) {
         debug_assert_eq!(amount, 0);
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+
+impl BufRead for StrReader<'_> {
+    type ReadError = core::convert::Infallible;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount)
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.reader.buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        self.reader.stream_position_hint()
+    }
 }
 
 impl BufWrite for Sink {
@@ -92,6 +224,10 @@ impl BufWrite for Sink {
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         Ok(())
     }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
 }
 
 impl BufRead for Null {
@@ -104,6 +240,10 @@ impl BufRead for Null {
     fn consume(&mut self, amount: usize) {
         debug_assert_eq!(amount, 0);
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
 }
 
 impl BufWrite for Null {
@@ -116,6 +256,10 @@ impl BufWrite for Null {
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         Ok(())
     }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -129,6 +273,18 @@ impl<T: BufRead + ?Sized> BufRead for alloc::boxed::Box<T> {
     fn consume(&mut self, amount: usize) {
         (**self).consume(amount)
     }
+
+    fn buffer(&self) -> &[u8] {
+        (**self).buffer()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        (**self).stream_position_hint()
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -139,9 +295,38 @@ impl<T: BufWrite + ?Sized> BufWrite for alloc::boxed::Box<T> {
         (**self).write_all(bytes)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        (**self).write_all_counting(bytes)
+    }
+
+    fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+        (**self).write_some(bytes)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        (**self).write_byte(byte)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        (**self).reserve(additional)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         (**self).flush()
     }
+
+    fn needs_flush(&self) -> bool {
+        (**self).needs_flush()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Seek + ?Sized> Seek for alloc::boxed::Box<T> {
+    type SeekError = T::SeekError;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::SeekError> {
+        (**self).seek(pos)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -153,9 +338,119 @@ impl BufWrite for alloc::vec::Vec<u8> {
         Ok(())
     }
 
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        self.push(byte);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        alloc::vec::Vec::reserve(self, additional)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         Ok(())
     }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
+}
+
+/// Reads the borrowed or owned bytes.
+///
+/// Note that consuming owned bytes shifts the remaining ones to the beginning of the vector so
+/// it's best to consume large chunks at once.
+#[cfg(feature = "alloc")]
+impl BufRead for alloc::borrow::Cow<'_, [u8]> {
+    type ReadError = core::convert::Infallible;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        Ok(self)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match self {
+            alloc::borrow::Cow::Borrowed(bytes) => bytes.consume(amount),
+            alloc::borrow::Cow::Owned(bytes) => { bytes.drain(..amount); },
+        }
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
+        let len = self.len();
+        match self {
+            alloc::borrow::Cow::Borrowed(bytes) => {
+                buf.extend_from_slice(bytes);
+                *bytes = &[];
+            },
+            alloc::borrow::Cow::Owned(bytes) => buf.append(bytes),
+        }
+        Ok(len)
+    }
+}
+
+/// Reads bytes from the front of the deque, removing them.
+///
+/// Since the deque is a ring buffer its contents may be split into two segments and `fill_buf`
+/// returns only the first one. Thus the consumer may need multiple calls to `fill_buf` to get all
+/// bytes even if no more bytes are added in between. An empty buffer means the deque is empty.
+#[cfg(feature = "alloc")]
+impl BufRead for alloc::collections::VecDeque<u8> {
+    type ReadError = core::convert::Infallible;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.as_slices().0.len());
+        self.drain(..amount);
+    }
+
+    fn buffer(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+/// Appends the bytes to the back of the deque.
+///
+/// Together with the `BufRead` implementation this makes the deque an in-memory pipe.
+#[cfg(feature = "alloc")]
+impl BufWrite for alloc::collections::VecDeque<u8> {
+    type WriteError = core::convert::Infallible;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+        self.extend(bytes);
+        Ok(())
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::WriteError> {
+        self.push_back(byte);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        alloc::collections::VecDeque::reserve(self, additional)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::WriteError> {
+        Ok(())
+    }
+
+    fn needs_flush(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "std")]
@@ -170,6 +465,10 @@ impl<T: std::io::Read> BufRead for std::io::BufReader<T> {
         std::io::BufRead::consume(self, amount)
     }
 
+    fn buffer(&self) -> &[u8] {
+        std::io::BufReader::buffer(self)
+    }
+
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
         std::io::Read::read_to_end(self, buf)
@@ -188,6 +487,16 @@ impl<T: AsRef<[u8]>> BufRead for std::io::Cursor<T> {
         std::io::BufRead::consume(self, amount)
     }
 
+    fn buffer(&self) -> &[u8] {
+        let data = self.get_ref().as_ref();
+        let position = usize::try_from(self.position()).unwrap_or(usize::MAX).min(data.len());
+        &data[position..]
+    }
+
+    fn stream_position_hint(&self) -> Option<u64> {
+        Some(self.position())
+    }
+
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
         std::io::Read::read_to_end(self, buf)
@@ -224,8 +533,19 @@ impl<T: std::io::BufRead> BufRead for std::io::Take<T> {
         std::io::BufRead::consume(self, amount)
     }
 
+    /// Returns the remaining limit as both bounds.
+    ///
+    /// The lower bound assumes the inner reader has at least `limit` bytes left, which is the
+    /// common case of reading a known-length part of a file.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let limit = usize::try_from(self.limit()).unwrap_or(usize::MAX);
+        (limit, Some(limit))
+    }
+
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize, Self::ReadError> {
+        // The limit may be much larger than the actual data so failing to reserve is not an error.
+        let _ = buf.try_reserve(self.size_hint().0);
         std::io::Read::read_to_end(self, buf)
     }
 }
@@ -274,11 +594,132 @@ impl<T: std::io::Write> BufWrite for std::io::BufWriter<T> {
         std::io::Write::write_all(self, bytes)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        write_all_counting(self, bytes)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         std::io::Write::flush(self)
     }
 }
 
+/// Implements `BufWrite` for cursors over fixed-size buffers.
+#[cfg(feature = "std")]
+macro_rules! impl_fixed_cursor_write {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> BufWrite for std::io::Cursor<$ty> {
+            type WriteError = BufferOverflow;
+
+            /// Writes the bytes at the current position of the cursor, advancing it.
+            ///
+            /// Unlike [`std::io::Write::write_all`] this doesn't write anything if the bytes don't
+            /// fit.
+            fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+                let len = self.get_ref().len();
+                let position = usize::try_from(self.position()).unwrap_or(usize::MAX).min(len);
+                let available = len - position;
+                if bytes.len() > available {
+                    return Err(BufferOverflow::new(bytes.len() - available));
+                }
+
+                self.get_mut()[position..(position + bytes.len())].copy_from_slice(bytes);
+                self.set_position((position + bytes.len()) as u64);
+                Ok(())
+            }
+
+            fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+                write_all_counting_fixed(self, bytes)
+            }
+
+            fn write_some(&mut self, bytes: &[u8]) -> Result<usize, Self::WriteError> {
+                let len = self.get_ref().len();
+                let position = usize::try_from(self.position()).unwrap_or(usize::MAX).min(len);
+                let len = bytes.len().min(len - position);
+                self.write_all(&bytes[..len])?;
+                Ok(len)
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+
+            fn needs_flush(&self) -> bool {
+                false
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_fixed_cursor_write!([const N: usize] [u8; N]);
+#[cfg(feature = "std")]
+impl_fixed_cursor_write!([] &mut [u8]);
+#[cfg(feature = "std")]
+impl_fixed_cursor_write!([] alloc::boxed::Box<[u8]>);
+
+/// Implements `BufWrite` for cursors over vectors which grow as needed.
+#[cfg(feature = "std")]
+macro_rules! impl_vec_cursor_write {
+    ($ty:ty) => {
+        impl BufWrite for std::io::Cursor<$ty> {
+            type WriteError = std::io::Error;
+
+            /// Writes the bytes at the current position of the cursor, advancing it.
+            ///
+            /// The vector is extended if needed. This only fails if the position doesn't fit into
+            /// `usize`.
+            fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::WriteError> {
+                std::io::Write::write_all(self, bytes)
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+
+            fn needs_flush(&self) -> bool {
+                false
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_vec_cursor_write!(alloc::vec::Vec<u8>);
+#[cfg(feature = "std")]
+impl_vec_cursor_write!(&mut alloc::vec::Vec<u8>);
+
+#[cfg(feature = "std")]
+impl<T: AsRef<[u8]>> Seek for std::io::Cursor<T> {
+    type SeekError = std::io::Error;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::SeekError> {
+        std::io::Seek::seek(self, pos.into())
+    }
+
+    fn stream_position(&mut self) -> Result<u64, Self::SeekError> {
+        Ok(self.position())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Read + std::io::Seek> Seek for std::io::BufReader<T> {
+    type SeekError = std::io::Error;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::SeekError> {
+        std::io::Seek::seek(self, pos.into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Write + std::io::Seek> Seek for std::io::BufWriter<T> {
+    type SeekError = std::io::Error;
+
+    /// Flushes the buffer and seeks the underlying writer.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::SeekError> {
+        std::io::Seek::seek(self, pos.into())
+    }
+}
+
 #[cfg(feature = "std")]
 impl BufWrite for std::io::Sink {
     type WriteError = std::io::Error;
@@ -287,6 +728,10 @@ impl BufWrite for std::io::Sink {
         std::io::Write::write_all(self, bytes)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        write_all_counting(self, bytes)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         std::io::Write::flush(self)
     }
@@ -300,6 +745,10 @@ impl BufWrite for std::io::StdoutLock<'_> {
         std::io::Write::write_all(self, bytes)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        write_all_counting(self, bytes)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         std::io::Write::flush(self)
     }
@@ -313,12 +762,17 @@ impl BufWrite for std::io::StderrLock<'_> {
         std::io::Write::write_all(self, bytes)
     }
 
+    fn write_all_counting(&mut self, bytes: &[u8]) -> Result<(), WriteAllError<Self::WriteError>> {
+        write_all_counting(self, bytes)
+    }
+
     fn flush(&mut self) -> Result<(), Self::WriteError> {
         std::io::Write::flush(self)
     }
 }
 
 #[cfg(feature = "std")]
+#[allow(clippy::needless_lifetimes)]
 fn fill_buf<'a, R: std::io::BufRead>(reader: &'a mut R) -> std::io::Result<&'a [u8]> {
     loop {
         match std::io::BufRead::fill_buf(reader) {
@@ -330,3 +784,339 @@ fn fill_buf<'a, R: std::io::BufRead>(reader: &'a mut R) -> std::io::Result<&'a [
         }
     }
 }
+
+/// Implements `write_all_counting` for fixed-size writers by writing as many bytes as fit.
+pub(crate) fn write_all_counting_fixed<W: BufWrite<WriteError=BufferOverflow> + ?Sized>(writer: &mut W, bytes: &[u8]) -> Result<(), WriteAllError<BufferOverflow>> {
+    let written = writer.write_some(bytes).map_err(|error| WriteAllError::new(0, error))?;
+    if written < bytes.len() {
+        return Err(WriteAllError::new(written, BufferOverflow::new(bytes.len() - written)));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn write_all_counting<W: std::io::Write>(writer: &mut W, mut bytes: &[u8]) -> Result<(), WriteAllError<std::io::Error>> {
+    let mut written = 0;
+    while !bytes.is_empty() {
+        match writer.write(bytes) {
+            Ok(0) => {
+                let error = std::io::Error::new(std::io::ErrorKind::WriteZero, "failed to write whole buffer");
+                return Err(WriteAllError::new(written, error));
+            },
+            Ok(amount) => {
+                written += amount;
+                bytes = &bytes[amount..];
+            },
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => (),
+            Err(error) => return Err(WriteAllError::new(written, error)),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufRead, BufWrite};
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    fn parse_pair(mut reader: impl BufRead<ReadError=core::convert::Infallible>) -> (u8, u8) {
+        let first = reader.read_byte().unwrap_or_else(|infallible| match infallible {});
+        let second = reader.read_byte().unwrap_or_else(|infallible| match infallible {});
+        (first.unwrap(), second.unwrap())
+    }
+
+    #[test]
+    fn reborrowed_slice_advances() {
+        let mut cursor = &[1, 2, 3, 4, 5][..];
+        assert_eq!(parse_pair(&mut cursor), (1, 2));
+        assert_eq!(cursor, &[3, 4, 5]);
+        assert_eq!((&mut cursor).read_byte().unwrap_or_else(|infallible| match infallible {}), Some(3));
+        assert_eq!(cursor, &[4, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn cow_read() {
+        use alloc::borrow::Cow;
+
+        let mut borrowed = Cow::Borrowed(&[1, 2, 3][..]);
+        assert_eq!(parse_pair(&mut borrowed), (1, 2));
+        assert_eq!(borrowed.buffer(), &[3]);
+
+        let mut owned: Cow<'_, [u8]> = Cow::Owned(alloc::vec![1, 2, 3]);
+        assert_eq!(parse_pair(&mut owned), (1, 2));
+        let mut buf = alloc::vec::Vec::new();
+        assert_eq!(owned.read_to_end(&mut buf).unwrap_or_else(|infallible| match infallible {}), 1);
+        assert_eq!(buf, [3]);
+        assert!(owned.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_deque_read_segments() {
+        // Filling the whole capacity and then replacing the front with new bytes forces the
+        // contents to wrap around.
+        let mut deque = alloc::collections::VecDeque::with_capacity(4);
+        let capacity = deque.capacity();
+        deque.extend((0..capacity).map(|i| i as u8));
+        deque.pop_front();
+        deque.pop_front();
+        deque.extend([100, 101]);
+        assert_eq!(deque.capacity(), capacity);
+        assert_eq!(deque.as_slices().1, [100, 101]);
+
+        let first = deque.fill_buf().unwrap_or_else(|infallible| match infallible {});
+        assert_eq!(first.len(), capacity - 2);
+        assert_eq!(first[0], 2);
+        deque.consume(capacity - 2);
+        assert_eq!(deque.fill_buf().unwrap_or_else(|infallible| match infallible {}), [100, 101]);
+        deque.consume(2);
+        assert!(deque.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_deque_pipe() {
+        let mut pipe = alloc::collections::VecDeque::new();
+        let mut buf = [0; 3];
+        for i in 0..10u8 {
+            crate::BufWrite::write_all(&mut pipe, &[i, i + 1, i + 2]).unwrap_or_else(|infallible| match infallible {});
+            crate::BufWrite::write_byte(&mut pipe, 42).unwrap_or_else(|infallible| match infallible {});
+            pipe.read_exact(&mut buf).unwrap();
+            assert_eq!(buf, [i, i + 1, i + 2]);
+            assert_eq!(pipe.read_byte().unwrap_or_else(|infallible| match infallible {}), Some(42));
+        }
+        assert!(pipe.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cursor_array_read() {
+        let mut reader = Cursor::new([1, 2, 3]);
+        reader.consume(1);
+        assert_eq!(reader.stream_position_hint(), Some(1));
+        assert_eq!(BufRead::fill_buf(&mut reader).unwrap(), &[2, 3]);
+        assert_eq!(reader.read_byte().unwrap(), Some(2));
+        assert_eq!(reader.position(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cursor_array_write() {
+        let mut writer = Cursor::new([0u8; 4]);
+        writer.write_all(&[1, 2, 3]).unwrap();
+        assert_eq!(writer.position(), 3);
+        assert!(writer.write_all(&[4, 5]).is_err());
+        assert_eq!(writer.position(), 3);
+        writer.write_all(&[4]).unwrap();
+        assert_eq!(writer.into_inner(), [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cursor_slice_round_trip() {
+        use crate::{Seek, SeekFrom};
+
+        let mut buf = [0u8; 4];
+        let mut cursor = Cursor::new(&mut buf[..]);
+        cursor.write_all(&[1, 2, 3]).unwrap();
+        assert!(cursor.write_all(&[4, 5]).is_err());
+        cursor.seek(SeekFrom::Start(1)).unwrap();
+        cursor.write_all(&[7]).unwrap();
+        cursor.seek(SeekFrom::Start(0)).unwrap();
+        let mut pair = [0; 2];
+        cursor.read_exact(&mut pair).unwrap();
+        assert_eq!(pair, [1, 7]);
+        assert_eq!(buf, [1, 7, 3, 0]);
+
+        let mut cursor = Cursor::new(alloc::vec![0u8; 2].into_boxed_slice());
+        cursor.write_all(&[1, 2]).unwrap();
+        assert!(cursor.write_all(&[3]).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cursor_vec_round_trip() {
+        use crate::{Seek, SeekFrom};
+
+        let mut cursor = Cursor::new(std::vec::Vec::new());
+        cursor.write_all(&[1, 2, 3]).unwrap();
+        cursor.seek(SeekFrom::Start(1)).unwrap();
+        cursor.write_all(&[7, 8, 9]).unwrap();
+        cursor.seek(SeekFrom::Start(0)).unwrap();
+        let mut pair = [0; 2];
+        cursor.read_exact(&mut pair).unwrap();
+        assert_eq!(pair, [1, 7]);
+        assert_eq!(cursor.into_inner(), [1, 7, 8, 9]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_seek() {
+        use crate::{Seek, SeekFrom};
+
+        let mut cursor = Cursor::new([1u8, 2, 3, 4]);
+        assert_eq!(cursor.seek(SeekFrom::End(-1)).unwrap(), 3);
+        assert_eq!(cursor.stream_position().unwrap(), 3);
+        assert_eq!(cursor.read_byte().unwrap(), Some(4));
+        assert_eq!(cursor.seek(SeekFrom::Current(-3)).unwrap(), 1);
+        assert_eq!(cursor.read_byte().unwrap(), Some(2));
+
+        let mut reader = std::io::BufReader::new(Cursor::new([1u8, 2, 3, 4]));
+        assert_eq!(reader.read_byte().unwrap(), Some(1));
+        assert_eq!(reader.stream_position().unwrap(), 1);
+        assert_eq!(reader.seek(SeekFrom::Start(3)).unwrap(), 3);
+        assert_eq!(reader.read_byte().unwrap(), Some(4));
+        assert_eq!(reader.read_byte().unwrap(), None);
+
+        let mut writer = std::io::BufWriter::new(Cursor::new([0u8; 4]));
+        writer.write_all(&[1, 2, 3]).unwrap();
+        assert_eq!(writer.stream_position().unwrap(), 3);
+        assert!(writer.buffer().is_empty());
+        assert_eq!(writer.seek(SeekFrom::Start(1)).unwrap(), 1);
+        writer.write_all(&[7]).unwrap();
+        assert_eq!(writer.into_inner().unwrap().into_inner(), [1, 7, 3, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_take_size_hint() {
+        let mut reader = std::io::Read::take(&[1u8, 2, 3, 4][..], 3);
+        assert_eq!(BufRead::size_hint(&reader), (3, Some(3)));
+        BufRead::consume(&mut reader, 1);
+        assert_eq!(BufRead::size_hint(&reader), (2, Some(2)));
+        let mut buf = std::vec::Vec::new();
+        assert_eq!(BufRead::read_to_end(&mut reader, &mut buf).unwrap(), 2);
+        assert_eq!(buf, [2, 3]);
+
+        let mut reader = std::io::Read::take(std::io::BufReader::new(&[1u8, 2][..]), 5);
+        assert_eq!(BufRead::size_hint(&reader), (5, Some(5)));
+        let mut buf = std::vec::Vec::new();
+        assert_eq!(BufRead::read_to_end(&mut reader, &mut buf).unwrap(), 2);
+        assert_eq!(buf, [1, 2]);
+        assert!(buf.capacity() >= 5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_reserve_through_adapters() {
+        let mut vec = alloc::vec::Vec::new();
+        let mut writer = alloc::boxed::Box::new((&mut vec).crc32().flush_every(16).map_write_err(drop));
+        writer.reserve(100);
+        writer.write_all(b"abc").unwrap();
+        drop(writer);
+        assert_eq!(vec, b"abc");
+        assert!(vec.capacity() >= 100);
+    }
+
+    #[test]
+    fn stream_position_hint() {
+        let mut reader = crate::read_str("hello world");
+        assert_eq!(reader.stream_position_hint(), Some(0));
+        let mut limited = (&mut reader).take(3);
+        limited.fill_buf().unwrap_or_else(|infallible| match infallible {});
+        limited.consume(2);
+        assert_eq!(limited.stream_position_hint(), Some(2));
+        let mut reader = reader.count_lines();
+        reader.fill_buf().unwrap_or_else(|infallible| match infallible {});
+        reader.consume(4);
+        assert_eq!(reader.stream_position_hint(), Some(6));
+        assert_eq!((&[1, 2][..]).stream_position_hint(), None);
+        #[cfg(feature = "std")]
+        {
+            let mut cursor = Cursor::new([1, 2, 3]);
+            BufRead::fill_buf(&mut cursor).unwrap();
+            BufRead::consume(&mut cursor, 2);
+            assert_eq!(BufRead::stream_position_hint(&cursor), Some(2));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_write_some() {
+        let mut buf = [0u8; 3];
+        let mut writer: alloc::boxed::Box<dyn BufWrite<WriteError=crate::error::BufferOverflow>> = alloc::boxed::Box::new(&mut buf[..]);
+        assert_eq!(writer.write_some(&[1, 2]).unwrap(), 2);
+        assert_eq!(writer.write_some(&[3, 4]).unwrap(), 1);
+        assert_eq!(writer.write_some(&[5]).unwrap(), 0);
+        drop(writer);
+        assert_eq!(buf, [1, 2, 3]);
+    }
+
+    #[test]
+    fn needs_flush() {
+        struct Unbuffered;
+
+        impl BufWrite for Unbuffered {
+            type WriteError = core::convert::Infallible;
+
+            fn write_all(&mut self, _bytes: &[u8]) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::WriteError> {
+                Ok(())
+            }
+        }
+
+        let mut buf = [0u8; 4];
+        let mut slice = &mut buf[..];
+        assert!(!slice.needs_flush());
+        assert!(!(&mut slice).map_write_err(drop).needs_flush());
+        assert!(!crate::sink().needs_flush());
+        assert!(Unbuffered.needs_flush());
+        assert!((&mut Unbuffered).map_write_err(drop).needs_flush());
+        #[cfg(feature = "alloc")]
+        {
+            assert!(!alloc::vec::Vec::new().needs_flush());
+            assert!(!alloc::boxed::Box::new(alloc::vec::Vec::new()).needs_flush());
+            assert!(alloc::boxed::Box::new(Unbuffered).needs_flush());
+        }
+    }
+
+    #[test]
+    fn slice_write_all_counting() {
+        let mut buf = [0u8; 4];
+        let mut writer = &mut buf[..];
+        writer.write_all_counting(&[1, 2]).unwrap();
+        let error = writer.write_all_counting(&[3, 4, 5]).unwrap_err();
+        assert_eq!(error.written(), 2);
+        assert!(writer.is_empty());
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_write_all_counting() {
+        /// Writer accepting two bytes per call until it's full.
+        struct Limited {
+            written: std::vec::Vec<u8>,
+        }
+
+        impl std::io::Write for Limited {
+            fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+                if self.written.len() >= 5 {
+                    return Err(std::io::ErrorKind::Other.into());
+                }
+                let len = bytes.len().min(2);
+                self.written.extend_from_slice(&bytes[..len]);
+                Ok(len)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = Limited { written: std::vec::Vec::new() };
+        let error = super::write_all_counting(&mut writer, &[1, 2, 3, 4, 5, 6, 7]).unwrap_err();
+        assert_eq!(error.written(), 6);
+        assert_eq!(writer.written, [1, 2, 3, 4, 5, 6]);
+
+        let mut cursor = Cursor::new([0u8; 3]);
+        let error = cursor.write_all_counting(&[1, 2, 3, 4]).unwrap_err();
+        assert_eq!(error.written(), 3);
+        assert_eq!(cursor.into_inner(), [1, 2, 3]);
+    }
+}
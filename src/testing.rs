@@ -0,0 +1,403 @@
+//! Helpers for testing code using the IO traits.
+//!
+//! This module is only available with the `test-utils` feature.
+
+use core::fmt;
+use crate::{BufRead, BufWrite};
+use crate::error::ReadExactError;
+
+/// Injects bytes into a stream at a given offset.
+///
+/// This reads the underlying reader up to `offset`, then returns the injected bytes and then
+/// continues with the underlying reader. It's useful for simulating corrupted frames in decoder
+/// tests. Multiple splice points can be achieved by nesting the readers, starting with the
+/// highest offset.
+///
+/// If the underlying reader ends before `offset` the injected bytes are not returned.
+pub struct SpliceReader<'a, R> {
+    reader: R,
+    before: u64,
+    injected: &'a [u8],
+}
+
+impl<'a, R: BufRead> SpliceReader<'a, R> {
+    /// Creates the reader injecting `injected` bytes after `offset` bytes of `reader`.
+    pub fn new(reader: R, offset: u64, injected: &'a [u8]) -> Self {
+        SpliceReader {
+            reader,
+            before: offset,
+            injected,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> BufRead for SpliceReader<'_, R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        if self.before > 0 {
+            let buf = self.reader.fill_buf()?;
+            let len = if (buf.len() as u64) < self.before { buf.len() } else { self.before as usize };
+            Ok(&buf[..len])
+        } else if !self.injected.is_empty() {
+            Ok(self.injected)
+        } else {
+            self.reader.fill_buf()
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if self.before > 0 {
+            assert!(amount as u64 <= self.before);
+            self.reader.consume(amount);
+            self.before -= amount as u64;
+        } else if !self.injected.is_empty() {
+            self.injected = &self.injected[amount..];
+        } else {
+            self.reader.consume(amount);
+        }
+    }
+}
+
+/// Returns the data of the underlying reader in chunks of scripted sizes.
+///
+/// The `k`-th chunk returned from `fill_buf` has at most `schedule[k]` bytes, the schedule is
+/// repeated when exhausted. A chunk ends once all of its bytes are consumed. This allows testing
+/// decoders against arbitrary fragmentation of the input, e.g. TCP segmentation.
+#[cfg(feature = "alloc")]
+pub struct ScriptedReader<R> {
+    reader: R,
+    schedule: alloc::vec::Vec<usize>,
+    index: usize,
+    limit: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: BufRead> ScriptedReader<R> {
+    /// Creates the reader returning chunks of `schedule` sizes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `schedule` is empty or contains zero since that would signal the end of the
+    /// stream.
+    pub fn new(reader: R, schedule: alloc::vec::Vec<usize>) -> Self {
+        assert!(!schedule.is_empty(), "the schedule must not be empty");
+        assert!(!schedule.contains(&0), "the schedule must not contain zero");
+        let limit = schedule[0];
+        ScriptedReader {
+            reader,
+            schedule,
+            index: 0,
+            limit,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: BufRead> BufRead for ScriptedReader<R> {
+    type ReadError = R::ReadError;
+
+    fn fill_buf(&mut self) -> Result<&[u8], Self::ReadError> {
+        let buf = self.reader.fill_buf()?;
+        Ok(&buf[..self.limit.min(buf.len())])
+    }
+
+    fn buffer(&self) -> &[u8] {
+        let buf = self.reader.buffer();
+        &buf[..self.limit.min(buf.len())]
+    }
+
+    fn consume(&mut self, amount: usize) {
+        assert!(amount <= self.limit);
+        self.reader.consume(amount);
+        self.limit -= amount;
+        if self.limit == 0 {
+            self.index = (self.index + 1) % self.schedule.len();
+            self.limit = self.schedule[self.index];
+        }
+    }
+}
+
+/// Checks that the reader provides `expected` bytes and upholds the [`BufRead`] contract.
+///
+/// The reader is driven through [`fill_buf`](BufRead::fill_buf) paired with full and partial
+/// [`consume`](BufRead::consume), [`read_byte`](BufRead::read_byte) and
+/// [`read_exact`](BufRead::read_exact) interleaved in a fixed pattern. It checks that:
+///
+/// * repeated `fill_buf` without `consume` returns the same bytes
+/// * `fill_buf` returns an empty buffer only at the end of the stream and keeps doing so
+/// * [`buffer`](BufRead::buffer) and [`size_hint`](BufRead::size_hint) are consistent with the
+///   remaining bytes
+///
+/// # Panics
+///
+/// Panics if the reader violates the contract, provides different bytes or fails.
+pub fn assert_bufread_contract<R: BufRead>(mut reader: R, expected: &[u8]) where R::ReadError: fmt::Debug {
+    let mut pos = 0;
+    let mut step = 0;
+    while pos < expected.len() {
+        let remaining = &expected[pos..];
+        check_hints(&reader, remaining, pos);
+        match step % 4 {
+            0 | 2 => {
+                let len = {
+                    let buf = reader.fill_buf().expect("fill_buf failed");
+                    assert!(!buf.is_empty(), "fill_buf reported end at offset {} but {} bytes remain", pos, remaining.len());
+                    assert!(remaining.starts_with(buf), "fill_buf returned unexpected bytes at offset {}", pos);
+                    buf.len()
+                };
+                let again = reader.fill_buf().expect("fill_buf failed");
+                assert_eq!(again, &remaining[..len], "repeated fill_buf returned different bytes at offset {}", pos);
+                check_hints(&reader, remaining, pos);
+                let amount = if step % 4 == 0 { len } else { (len / 2).max(1) };
+                reader.consume(amount);
+                pos += amount;
+            },
+            1 => {
+                let byte = reader.read_byte().expect("read_byte failed");
+                assert_eq!(byte, Some(remaining[0]), "read_byte returned unexpected byte at offset {}", pos);
+                pos += 1;
+            },
+            _ => {
+                let mut buf = [0u8; 3];
+                let len = remaining.len().min(buf.len());
+                match reader.read_exact(&mut buf[..len]) {
+                    Ok(()) => (),
+                    Err(ReadExactError::UnexpectedEnd(_)) => panic!("read_exact reported end at offset {} but {} bytes remain", pos, remaining.len()),
+                    Err(ReadExactError::ReadingFailed(error)) => panic!("read_exact failed: {:?}", error),
+                }
+                assert_eq!(buf[..len], remaining[..len], "read_exact returned unexpected bytes at offset {}", pos);
+                pos += len;
+            },
+        }
+        step += 1;
+    }
+    check_hints(&reader, &[], pos);
+    for _ in 0..2 {
+        assert!(reader.fill_buf().expect("fill_buf failed").is_empty(), "fill_buf returned bytes past the end");
+    }
+    assert_eq!(reader.read_byte().expect("read_byte failed"), None, "read_byte returned a byte past the end");
+    match reader.read_exact(&mut [0]) {
+        Ok(()) => panic!("read_exact succeeded past the end"),
+        Err(ReadExactError::UnexpectedEnd(_)) => (),
+        Err(ReadExactError::ReadingFailed(error)) => panic!("read_exact failed: {:?}", error),
+    }
+}
+
+fn check_hints<R: BufRead>(reader: &R, remaining: &[u8], pos: usize) {
+    let buffer = reader.buffer();
+    assert!(buffer.len() <= remaining.len(), "buffer returned bytes past the end");
+    assert!(remaining.starts_with(buffer), "buffer returned unexpected bytes at offset {}", pos);
+    let (lower, upper) = reader.size_hint();
+    assert!(lower <= remaining.len(), "size_hint lower bound {} exceeds remaining {} bytes", lower, remaining.len());
+    if let Some(upper) = upper {
+        assert!(upper >= remaining.len(), "size_hint upper bound {} is below remaining {} bytes", upper, remaining.len());
+    }
+}
+
+/// Reads readers created by `make` using all reading methods and checks the results match.
+///
+/// The data is read using [`read_to_end`](BufRead::read_to_end), a [`read_byte`](BufRead::read_byte)
+/// loop, [`fill_buf`](BufRead::fill_buf) with full and single-byte
+/// [`consume`](BufRead::consume) and chunked [`read_exact`](BufRead::read_exact). Then the
+/// result is verified using [`assert_bufread_contract`] on another reader. Returns the read
+/// bytes so they can be compared to the expected data.
+///
+/// # Panics
+///
+/// Panics if the methods return different data, reading fails or the contract is violated.
+#[cfg(feature = "alloc")]
+pub fn read_all_ways<R: BufRead, F: FnMut() -> R>(mut make: F) -> alloc::vec::Vec<u8> where R::ReadError: fmt::Debug {
+    use alloc::vec::Vec;
+
+    let mut expected = Vec::new();
+    make().read_to_end(&mut expected).expect("read_to_end failed");
+
+    let mut reader = make();
+    let mut bytes = Vec::new();
+    while let Some(byte) = reader.read_byte().expect("read_byte failed") {
+        bytes.push(byte);
+    }
+    assert_eq!(bytes, expected, "read_byte loop and read_to_end returned different bytes");
+
+    for consume_all in [true, false] {
+        let mut reader = make();
+        let mut bytes = Vec::new();
+        loop {
+            let buf = reader.fill_buf().expect("fill_buf failed");
+            if buf.is_empty() {
+                break;
+            }
+            let amount = if consume_all { buf.len() } else { 1 };
+            bytes.extend_from_slice(&buf[..amount]);
+            reader.consume(amount);
+        }
+        assert_eq!(bytes, expected, "fill_buf/consume and read_to_end returned different bytes");
+    }
+
+    let mut reader = make();
+    let mut bytes = Vec::new();
+    loop {
+        let mut chunk = [0u8; 7];
+        match reader.read_exact(&mut chunk) {
+            Ok(()) => bytes.extend_from_slice(&chunk),
+            Err(ReadExactError::UnexpectedEnd(error)) => {
+                bytes.extend_from_slice(&chunk[..error.available()]);
+                break;
+            },
+            Err(ReadExactError::ReadingFailed(error)) => panic!("read_exact failed: {:?}", error),
+        }
+    }
+    assert_eq!(bytes, expected, "read_exact and read_to_end returned different bytes");
+
+    assert_bufread_contract(make(), &expected);
+    expected
+}
+
+/// Checks that writers created by `make` produce the same output regardless of chunking.
+///
+/// The same data is written to fresh writers all at once, byte by byte, in chunks of varying
+/// sizes interleaved with empty writes, using [`write_some`](BufWrite::write_some) and with a
+/// flush after each chunk. Each writer is then flushed twice to check that flushing is
+/// idempotent and passed to `verify` together with the expected output. `verify` should
+/// extract the written bytes from the writer and assert they are equal to the expected ones.
+///
+/// The data is 300 bytes long so the writers need to be able to hold at least this many bytes.
+///
+/// # Panics
+///
+/// Panics if writing or flushing fails or if `write_some` doesn't make progress. `verify` is
+/// expected to panic if the output doesn't match.
+pub fn assert_bufwrite_contract<W, F, V>(make: F, mut verify: V) where W: BufWrite, W::WriteError: fmt::Debug, F: Fn() -> W, V: FnMut(W, &[u8]) {
+    let mut data = [0u8; 300];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = (i * 7 + i / 256) as u8;
+    }
+
+    let finish = |mut writer: W, verify: &mut V| {
+        writer.flush().expect("flush failed");
+        writer.flush().expect("repeated flush failed");
+        verify(writer, &data);
+    };
+
+    let mut writer = make();
+    writer.write_all(&data).expect("write_all failed");
+    finish(writer, &mut verify);
+
+    let mut writer = make();
+    for byte in &data {
+        writer.write_all(core::slice::from_ref(byte)).expect("write_all failed");
+    }
+    finish(writer, &mut verify);
+
+    for flush_each in [false, true] {
+        let mut writer = make();
+        let mut rest = &data[..];
+        let mut chunk_len = 0;
+        while !rest.is_empty() {
+            chunk_len = chunk_len % 13 + 1;
+            let (chunk, tail) = rest.split_at(chunk_len.min(rest.len()));
+            writer.write_all(&[]).expect("empty write_all failed");
+            writer.write_all(chunk).expect("write_all failed");
+            if flush_each {
+                writer.flush().expect("flush failed");
+            }
+            rest = tail;
+        }
+        finish(writer, &mut verify);
+    }
+
+    let mut writer = make();
+    let mut rest = &data[..];
+    while !rest.is_empty() {
+        let written = writer.write_some(rest).expect("write_some failed");
+        assert!(written > 0, "write_some made no progress");
+        assert!(written <= rest.len(), "write_some reported more bytes than provided");
+        rest = &rest[written..];
+    }
+    finish(writer, &mut verify);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufRead;
+    use super::SpliceReader;
+
+    #[test]
+    fn splice() {
+        let chunks: [&[u8]; 2] = [&[1, 2, 3], &[4, 5]];
+        let reader = crate::from_slices(chunks.iter().copied());
+        let reader = SpliceReader::new(reader, 4, &[42]);
+        let mut reader = SpliceReader::new(reader, 1, &[7, 8]);
+        let mut buf = [0; 8];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 7, 8, 2, 3, 4, 42, 5]);
+        assert!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}).is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn scripted() {
+        let mut reader = super::ScriptedReader::new(&[1, 2, 3, 4, 5, 6][..], alloc::vec![1, 2]);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [1]);
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [2, 3]);
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [3]);
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap_or_else(|infallible| match infallible {}), [4]);
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [4, 5, 6]);
+    }
+
+    #[test]
+    fn bufread_contract() {
+        let chunks: [&[u8]; 4] = [&[1, 2, 3], &[4], &[5, 6, 7, 8, 9], &[10]];
+        super::assert_bufread_contract(crate::from_slices(chunks.iter().copied()), &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        super::assert_bufread_contract(&[][..], &[]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn all_ways() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17];
+        let bytes = super::read_all_ways(|| super::ScriptedReader::new(&data[..], alloc::vec![3, 1, 4]));
+        assert_eq!(bytes, data);
+    }
+
+    #[test]
+    #[should_panic(expected = "bytes past the end")]
+    fn bufread_contract_violation() {
+        super::assert_bufread_contract(&[1, 2][..], &[1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bufwrite_contract() {
+        let mut calls = 0;
+        super::assert_bufwrite_contract(alloc::vec::Vec::new, |vec, expected| {
+            assert_eq!(vec, expected);
+            calls += 1;
+        });
+        assert_eq!(calls, 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn bufwrite_contract_cursor() {
+        super::assert_bufwrite_contract(|| std::io::Cursor::new([0u8; 300]), |cursor, expected| {
+            assert_eq!(&cursor.get_ref()[..(cursor.position() as usize)], expected);
+        });
+    }
+}